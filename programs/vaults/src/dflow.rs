@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::{token, token_2022};
+
+use crate::errors::FundError;
+
+/// Length of an SPL token account
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Length of an SPL token mint
+const MINT_LEN: usize = 82;
+
+/// Account reference inside a DFlow instruction (mirrors `AccountMeta`)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DFlowAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Single instruction of a serialized DFlow swap bundle
+/// Built off-chain from the DFlow trade API response
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DFlowInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<DFlowAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// Validate a DFlow instruction bundle and execute it with vault authority seeds
+///
+/// Every instruction must target the whitelisted DFlow program, every SPL token
+/// account it references must be owned by the vault authority (Token-2022
+/// accounts are rejected - the vault only holds classic SPL Token ATAs), only
+/// the token accounts in `writable_token_accounts` may be written, and the vault
+/// authority is the only signer the program will lend to the bundle.
+/// The lent signature must not reach anything else the fund controls: mints
+/// with the vault authority as mint or freeze authority (the share mint) are
+/// rejected outright, and neither the vault authority nor accounts owned by
+/// this program may be writable.
+/// `account_infos` must contain every account referenced by the bundle
+/// (including the DFlow program itself).
+pub fn validate_and_execute_bundle<'info>(
+    bundle: &[DFlowInstruction],
+    allowed_program: &Pubkey,
+    vault_authority: &AccountInfo<'info>,
+    writable_token_accounts: &[Pubkey],
+    account_infos: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require!(!bundle.is_empty(), FundError::InvalidTradeInstruction);
+
+    // Validate the whole bundle before executing anything
+    for ix in bundle {
+        validate_instruction(ix, allowed_program, vault_authority.key, writable_token_accounts, account_infos)?;
+    }
+
+    for ix in bundle {
+        let instruction = Instruction {
+            program_id: ix.program_id,
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: ix.data.clone(),
+        };
+
+        invoke_signed(&instruction, account_infos, signer_seeds)?;
+    }
+
+    msg!("Executed DFlow bundle: {} instruction(s)", bundle.len());
+
+    Ok(())
+}
+
+fn validate_instruction(
+    ix: &DFlowInstruction,
+    allowed_program: &Pubkey,
+    vault_authority: &Pubkey,
+    writable_token_accounts: &[Pubkey],
+    account_infos: &[AccountInfo],
+) -> Result<()> {
+    // Program must be the whitelisted DFlow program
+    require_keys_eq!(ix.program_id, *allowed_program, FundError::InvalidDFlowProgram);
+    require!(
+        find_account(account_infos, &ix.program_id).is_some(),
+        FundError::InvalidTradeInstruction
+    );
+
+    for meta in &ix.accounts {
+        let info = find_account(account_infos, &meta.pubkey)
+            .ok_or(FundError::InvalidTradeInstruction)?;
+
+        // Only the vault authority can be signed for by this program
+        if meta.is_signer && meta.pubkey != *vault_authority && !info.is_signer {
+            return Err(FundError::InvalidTradeInstruction.into());
+        }
+
+        // Fund state and the vault authority itself are never writable
+        if meta.is_writable {
+            require!(
+                meta.pubkey != *vault_authority && info.owner != &crate::ID,
+                FundError::FundAccountInBundle
+            );
+        }
+
+        // Mints the vault authority controls must not be reachable at all
+        require!(
+            !mint_controlled_by(info, vault_authority)?,
+            FundError::FundAccountInBundle
+        );
+
+        // All SPL token accounts must be vault-owned, and only the accounts
+        // whose balances the caller checks afterwards may be written
+        if let Some(owner) = token_account_owner(info)? {
+            require_keys_eq!(owner, *vault_authority, FundError::InvalidTokenAccountOwner);
+            if meta.is_writable {
+                require!(
+                    writable_token_accounts.contains(&meta.pubkey),
+                    FundError::UnexpectedWritableTokenAccount
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn find_account<'a, 'info>(
+    account_infos: &'a [AccountInfo<'info>],
+    key: &Pubkey,
+) -> Option<&'a AccountInfo<'info>> {
+    account_infos.iter().find(|info| info.key == key)
+}
+
+/// Returns the token owner if `info` is an SPL token account
+/// Fails on Token-2022 accounts: vault USDC and outcome tokens are held in
+/// classic SPL Token accounts only, so nothing else may slip past the owner check
+pub fn token_account_owner(info: &AccountInfo) -> Result<Option<Pubkey>> {
+    require_keys_neq!(*info.owner, token_2022::ID, FundError::UnsupportedTokenProgram);
+    if info.owner != &token::ID {
+        return Ok(None);
+    }
+
+    let data = info.try_borrow_data()?;
+    if data.len() != TOKEN_ACCOUNT_LEN {
+        // Mints and multisigs carry no token owner
+        return Ok(None);
+    }
+
+    // Layout: mint (32) | owner (32) | ...
+    let owner = Pubkey::try_from(&data[32..64]).map_err(|_| FundError::InvalidTradeInstruction)?;
+    Ok(Some(owner))
+}
+
+/// Whether `info` is an SPL token mint with `authority` as its mint or freeze
+/// authority
+fn mint_controlled_by(info: &AccountInfo, authority: &Pubkey) -> Result<bool> {
+    if info.owner != &token::ID {
+        return Ok(false);
+    }
+
+    let data = info.try_borrow_data()?;
+    if data.len() != MINT_LEN {
+        return Ok(false);
+    }
+
+    let mint = token::Mint::try_deserialize(&mut &data[..])?;
+    Ok(mint.mint_authority == Some(*authority).into()
+        || mint.freeze_authority == Some(*authority).into())
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+/// Anchor error codes are positional: new variants go at the end
 #[error_code]
 pub enum FundError {
     // === Stage Errors ===
@@ -121,4 +122,177 @@ pub enum FundError {
     
     #[msg("No pending withdrawals to process")]
     NoPendingWithdrawals,
+    
+    // === Fee Errors (cont.) ===
+    #[msg("Management fee exceeds protocol maximum")]
+    MgmtFeeExceedsMax,
+    
+    #[msg("No management fee shares to collect")]
+    NoManagementFeeToCollect,
+    
+    #[msg("Invalid fee escrow account")]
+    InvalidFeeEscrow,
+    
+    #[msg("No fees to claim")]
+    NoFeesToClaim,
+    
+    #[msg("Only the protocol fee recipient can claim protocol fees")]
+    UnauthorizedFeeRecipient,
+    
+    // === Authorization Errors (cont.) ===
+    #[msg("Only the pending admin can accept the admin role")]
+    UnauthorizedPendingAdmin,
+    
+    #[msg("Invalid protocol config value")]
+    InvalidProtocolConfig,
+    
+    // === Pause Errors ===
+    #[msg("Operation is paused")]
+    OperationPaused,
+    
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    
+    // === Position Errors (cont.) ===
+    #[msg("Position quantity is insufficient for this reduction")]
+    InsufficientPosition,
+    
+    #[msg("Position belongs to a different market")]
+    PositionMarketMismatch,
+    
+    #[msg("Outcome redemption paid less than par")]
+    RedemptionBelowPar,
+    
+    #[msg("Outcome redemption must be a single instruction")]
+    InvalidRedemptionBundle,
+    
+    // === DFlow Errors (cont.) ===
+    #[msg("Only classic SPL Token accounts are supported")]
+    UnsupportedTokenProgram,
+    
+    #[msg("Bundle writes to a vault token account it does not declare")]
+    UnexpectedWritableTokenAccount,
+    
+    #[msg("Invalid trade parameters")]
+    InvalidTradeParams,
+    
+    #[msg("Unknown outcome side")]
+    InvalidSide,
+    
+    #[msg("Unknown trade direction")]
+    InvalidDirection,
+    
+    #[msg("Outcome mint must use 6 decimals")]
+    InvalidOutcomeMint,
+    
+    #[msg("Market is not listed in the protocol allowlist")]
+    MarketNotAllowed,
+    
+    #[msg("Market is suspended")]
+    MarketSuspended,
+    
+    #[msg("Market has not resolved")]
+    MarketNotResolved,
+    
+    #[msg("Market is already resolved")]
+    MarketAlreadyResolved,
+    
+    #[msg("Market still has open fund positions")]
+    MarketHasOpenPositions,
+    
+    // === Mandate Errors ===
+    #[msg("Invalid mandate parameters")]
+    InvalidMandate,
+    
+    #[msg("Fund mandate account is required")]
+    MandateMissing,
+    
+    #[msg("Trade violates the fund mandate")]
+    MandateViolation,
+    
+    // === Drawdown Errors ===
+    #[msg("Max drawdown must be between 0 and 10000 bps")]
+    InvalidDrawdownLimit,
+    
+    #[msg("Drawdown breaker active: only position-reducing trades allowed")]
+    DrawdownBreached,
+    
+    #[msg("Fund NAV is above the drawdown floor")]
+    DrawdownNotBreached,
+    
+    #[msg("Outcome token account does not match the traded side's mint")]
+    OutcomeMintMismatch,
+    
+    // === Slippage Errors ===
+    #[msg("Trade spent more than the declared amount")]
+    TradeAmountExceeded,
+    
+    #[msg("Trade output below the declared minimum")]
+    InsufficientTradeOutput,
+    
+    #[msg("Trade price deviates beyond slippage tolerance")]
+    SlippageExceeded,
+    
+    #[msg("Trade price is worse than the limit price")]
+    LimitPriceExceeded,
+    
+    // === Pricing Errors ===
+    #[msg("Only the protocol oracle can publish prices")]
+    UnauthorizedOracle,
+    
+    #[msg("Price exceeds the maximum outcome price")]
+    InvalidPrice,
+    
+    #[msg("Market price is stale")]
+    StalePrice,
+    
+    #[msg("Market price confidence interval too wide")]
+    PriceConfidenceTooWide,
+    
+    #[msg("Fund NAV is zero - shares cannot be priced")]
+    ZeroNav,
+    
+    #[msg("NAV accounts do not match the fund's open positions")]
+    NavAccountsMismatch,
+    
+    // === Buffer Errors (cont.) ===
+    #[msg("Liquidity buffer below protocol minimum")]
+    BufferBelowMinimum,
+    
+    #[msg("Epoch interval out of bounds")]
+    InvalidEpochInterval,
+    
+    // === Withdrawal Queue Errors (cont.) ===
+    #[msg("Invalid withdrawal batch accounts")]
+    InvalidWithdrawalBatch,
+    
+    #[msg("Epoch is no longer the latest struck epoch")]
+    EpochClosed,
+    
+    #[msg("Withdrawal request has been priced by a struck epoch")]
+    WithdrawalAlreadyStruck,
+    
+    #[msg("Epoch redemption gate must be at most 10000 bps")]
+    InvalidRedemptionGate,
+    
+    #[msg("Withdrawal request must be completed or cancelled and behind the queue head")]
+    WithdrawalRequestNotClosable,
+    
+    #[msg("Withdrawal requests still hold escrowed shares")]
+    WithdrawalQueueNotDrained,
+    
+    // === Subscription Queue Errors ===
+    #[msg("Deposit request epoch has already been struck")]
+    DepositEpochStruck,
+    
+    #[msg("Invalid deposit request batch")]
+    InvalidDepositBatch,
+    
+    // === DFlow Errors (cont.) ===
+    #[msg("Bundle references an account controlled by the fund")]
+    FundAccountInBundle,
+    
+    // === Authorization Errors (cont.) ===
+    #[msg("Only the investor who made the request can perform this action")]
+    UnauthorizedInvestor,
 }
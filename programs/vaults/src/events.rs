@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when process_epoch strikes an epoch
+/// `gated_usdc` is the redemption value held back by the per-epoch gate and
+/// rolled to the next epoch
+#[event]
+pub struct EpochStruck {
+    pub fund: Pubkey,
+    pub epoch: u64,
+    pub nav: u64,
+    pub nav_per_share: u64,
+    pub pending_shares: u64,
+    pub usdc_available: u64,
+    pub gated_usdc: u64,
+    pub fill_ratio: u64,
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, MintTo, Transfer};
+
+use crate::state::{FeeLedger, FundState, ProtocolConfig, VAULT_AUTHORITY_SEED};
+
+/// Accounts used to mint accrued management fee shares into the fund's
+/// management fee escrow
+pub struct MgmtFeeAccounts<'info> {
+    pub token_program: AccountInfo<'info>,
+    pub share_mint: AccountInfo<'info>,
+    pub mgmt_fee_escrow: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+}
+
+impl<'info> MgmtFeeAccounts<'info> {
+    /// Accrue the management fee up to `now` and mint the accrued shares, so
+    /// the share mint supply always matches `total_shares`. Returns the
+    /// shares minted.
+    ///
+    /// The shares go to a vault authority owned escrow rather than a manager
+    /// account: a closed manager account must not block investor handlers.
+    /// The manager claims them with collect_management_fee.
+    pub fn accrue(&self, fund: &mut Account<'info, FundState>, now: i64) -> Result<u64> {
+        fund.accrue_management_fee(now)?;
+        let shares = fund.unminted_mgmt_fee_shares;
+        if shares == 0 {
+            return Ok(0);
+        }
+
+        let fund_key = fund.key();
+        let seeds = &[
+            VAULT_AUTHORITY_SEED,
+            fund_key.as_ref(),
+            &[fund.vault_authority_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let mint_ctx = CpiContext::new_with_signer(
+            self.token_program.clone(),
+            MintTo {
+                mint: self.share_mint.clone(),
+                to: self.mgmt_fee_escrow.clone(),
+                authority: self.vault_authority.clone(),
+            },
+            signer_seeds,
+        );
+        token::mint_to(mint_ctx, shares)?;
+
+        fund.unminted_mgmt_fee_shares = 0;
+        msg!("Management fee accrued: {} shares", shares);
+
+        Ok(shares)
+    }
+}
+
+/// Accounts used to move a fund's performance fee into its fee escrow
+pub struct PerfFeeAccounts<'a, 'info> {
+    pub token_program: AccountInfo<'info>,
+    pub vault_usdc_ata: AccountInfo<'info>,
+    pub fee_escrow: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+impl<'a, 'info> PerfFeeAccounts<'a, 'info> {
+    /// Pay the crystallized, unpaid performance fee (if any) from the vault
+    /// into the fee escrow, credit the ledger with the manager and protocol
+    /// shares and mark the fee paid. Returns the fee paid.
+    pub fn pay(
+        &self,
+        fund: &mut FundState,
+        config: &ProtocolConfig,
+        fee_ledger: &mut FeeLedger,
+    ) -> Result<u64> {
+        let perf_fee = fund.outstanding_perf_fee();
+        if perf_fee == 0 {
+            return Ok(0);
+        }
+
+        let transfer_fee_ctx = CpiContext::new_with_signer(
+            self.token_program.clone(),
+            Transfer {
+                from: self.vault_usdc_ata.clone(),
+                to: self.fee_escrow.clone(),
+                authority: self.vault_authority.clone(),
+            },
+            self.signer_seeds,
+        );
+        token::transfer(transfer_fee_ctx, perf_fee)?;
+
+        let protocol_fee = config.protocol_share(perf_fee);
+        let manager_fee = perf_fee - protocol_fee;
+        fee_ledger.credit_perf_fee(perf_fee, protocol_fee)?;
+
+        fund.perf_fee_paid = true;
+        msg!("Performance fee paid: {} USDC (manager {}, protocol {})", perf_fee, manager_fee, protocol_fee);
+
+        Ok(perf_fee)
+    }
+}
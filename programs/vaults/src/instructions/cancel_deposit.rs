@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
+
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{
+    DepositRequest, FundState,
+    FUND_SEED, VAULT_AUTHORITY_SEED, DEPOSIT_REQUEST_SEED, DEPOSIT_ESCROW_SEED,
+    ProtocolConfig, PROTOCOL_CONFIG_SEED, require_not_admin_paused, PAUSE_DEPOSITS,
+};
+use crate::errors::FundError;
+
+/// Cancel a deposit request before its epoch is struck
+/// Escrowed USDC and the request rent are returned to the investor. A struck
+/// request is committed: it is minted at the latest strike by process_deposits
+/// (if the fund closes first, at the next season's first strike).
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct CancelDeposit<'info> {
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            DEPOSIT_REQUEST_SEED,
+            fund_state.key().as_ref(),
+            investor.key().as_ref(),
+            &epoch.to_le_bytes()
+        ],
+        bump = deposit_request.bump,
+        constraint = deposit_request.epoch == fund_state.current_epoch @ FundError::DepositEpochStruck,
+        close = investor
+    )]
+    pub deposit_request: Box<Account<'info, DepositRequest>>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Deposit escrow
+    #[account(
+        mut,
+        seeds = [DEPOSIT_ESCROW_SEED, fund_state.key().as_ref()],
+        bump
+    )]
+    pub deposit_escrow: Account<'info, TokenAccount>,
+
+    /// Investor's USDC token account (receives the escrowed USDC)
+    #[account(
+        mut,
+        constraint = investor_usdc_ata.mint == fund_state.usdc_mint @ FundError::InvalidUsdcMint
+    )]
+    pub investor_usdc_ata: Account<'info, TokenAccount>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CancelDeposit>, epoch: u64) -> Result<()> {
+    // A manager pause cannot hold escrowed funds back from investors
+    require_not_admin_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_DEPOSITS)?;
+
+    // Accrue the management fee on every state change
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    let amount = ctx.accounts.deposit_request.usdc_amount;
+
+    // Return escrowed USDC to the investor
+    let fund_key = ctx.accounts.fund_state.key();
+    let vault_seeds = &[
+        VAULT_AUTHORITY_SEED,
+        fund_key.as_ref(),
+        &[ctx.accounts.fund_state.vault_authority_bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.deposit_escrow.to_account_info(),
+            to: ctx.accounts.investor_usdc_ata.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer(transfer_ctx, amount)?;
+
+    // Update fund state
+    let fund = &mut ctx.accounts.fund_state;
+    fund.pending_deposit_usdc = fund.pending_deposit_usdc.saturating_sub(amount);
+
+    msg!("Deposit request for epoch {} cancelled", epoch);
+    msg!("USDC returned to investor: {}", amount);
+
+    Ok(())
+}
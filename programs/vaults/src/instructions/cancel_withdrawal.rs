@@ -1,15 +1,23 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
 
+use crate::fees::MgmtFeeAccounts;
 use crate::state::{
-    FundState, FundStage, WithdrawalRequest, RequestStatus,
-    FUND_SEED, WITHDRAWAL_REQUEST_SEED,
+    FundState, FundStage, WithdrawalPricing, WithdrawalQueue, WithdrawalRequest, RequestStatus,
+    FUND_SEED, VAULT_AUTHORITY_SEED, WITHDRAWAL_QUEUE_SEED, WITHDRAWAL_REQUEST_SEED,
+    ProtocolConfig, PROTOCOL_CONFIG_SEED, require_not_admin_paused, PAUSE_WITHDRAWALS,
 };
 use crate::errors::FundError;
 
 /// Cancel a pending withdrawal request
-/// Only allowed if not partially filled
+/// Only allowed if not partially filled - escrowed shares are returned and the
+/// request is marked Cancelled (its rent is reclaimed once the queue head passes it)
+/// Under forward pricing, only until the request's epoch has been struck
+/// Once the fund is Closed no epoch fills the queue any more, so any active
+/// request - partially filled or struck - can be cancelled for its unfilled
+/// shares, which holders then redeem at the final NAV
 #[derive(Accounts)]
-#[instruction(request_index: u32)]
+#[instruction(sequence: u64)]
 pub struct CancelWithdrawal<'info> {
     #[account(mut)]
     pub investor: Signer<'info>,
@@ -21,42 +29,142 @@ pub struct CancelWithdrawal<'info> {
     )]
     pub fund_state: Box<Account<'info, FundState>>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     #[account(
         mut,
         seeds = [
             WITHDRAWAL_REQUEST_SEED,
             fund_state.key().as_ref(),
-            investor.key().as_ref(),
-            &request_index.to_le_bytes()
+            &sequence.to_le_bytes()
         ],
         bump = withdrawal_request.bump,
-        constraint = withdrawal_request.investor == investor.key() @ FundError::UnauthorizedManager,
-        constraint = withdrawal_request.status == RequestStatus::Pending @ FundError::WithdrawalRequestInactive,
-        close = investor
+        constraint = withdrawal_request.investor == investor.key() @ FundError::UnauthorizedInvestor,
+        constraint = !withdrawal_request.is_terminal() @ FundError::WithdrawalRequestInactive
     )]
     pub withdrawal_request: Box<Account<'info, WithdrawalRequest>>,
 
+    /// FIFO withdrawal queue (the head moves past the cancelled request)
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_QUEUE_SEED, fund_state.key().as_ref()],
+        bump = withdrawal_queue.bump
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Share mint
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Share escrow (vault authority's share ATA)
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = vault_authority,
+    )]
+    pub share_escrow: Account<'info, TokenAccount>,
+
+    /// Investor's share token account (receives escrowed shares)
+    #[account(
+        mut,
+        constraint = investor_shares.mint == fund_state.share_mint @ FundError::InvalidShareMint,
+        constraint = investor_shares.owner == investor.key()
+    )]
+    pub investor_shares: Account<'info, TokenAccount>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<CancelWithdrawal>, request_index: u32) -> Result<()> {
+pub fn handler(ctx: Context<CancelWithdrawal>, _sequence: u64) -> Result<()> {
+    // A manager pause cannot hold escrowed funds back from investors
+    require_not_admin_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_WITHDRAWALS)?;
+
+    // Accrue the management fee on every state change
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
     let request = &ctx.accounts.withdrawal_request;
-    
-    // Can't cancel if partially filled
-    require!(
-        request.shares_filled == 0,
-        FundError::CannotCancelPartialWithdrawal
+    let fund = &ctx.accounts.fund_state;
+
+    // After close nothing fills the queue - every remainder can be returned
+    if fund.stage != FundStage::Closed {
+        // Can't cancel if partially filled
+        require!(
+            request.shares_filled == 0,
+            FundError::CannotCancelPartialWithdrawal
+        );
+
+        // Once struck, the investor knows the fill price - no cancelling against it
+        if fund.withdrawal_pricing == WithdrawalPricing::ForwardEpochNav {
+            require!(
+                request.epoch == fund.current_epoch,
+                FundError::WithdrawalAlreadyStruck
+            );
+        }
+    }
+
+    let shares_to_return = request.shares_remaining();
+    let locked_value = request.locked_value();
+
+    // Return escrowed shares to the investor
+    let fund_key = ctx.accounts.fund_state.key();
+    let vault_seeds = &[
+        VAULT_AUTHORITY_SEED,
+        fund_key.as_ref(),
+        &[ctx.accounts.fund_state.vault_authority_bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.share_escrow.to_account_info(),
+            to: ctx.accounts.investor_shares.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        signer_seeds,
     );
+    transfer(transfer_ctx, shares_to_return)?;
 
-    let shares_to_return = request.shares_requested;
+    ctx.accounts.withdrawal_request.status = RequestStatus::Cancelled;
+    ctx.accounts.withdrawal_queue.advance_head(&ctx.accounts.withdrawal_request);
 
     // Update fund state
     let fund = &mut ctx.accounts.fund_state;
     fund.pending_withdrawal_shares = fund.pending_withdrawal_shares
         .saturating_sub(shares_to_return);
+    fund.pending_withdrawal_locked_usdc = fund.pending_withdrawal_locked_usdc
+        .saturating_sub(locked_value);
 
     msg!("Withdrawal request cancelled");
-    msg!("Shares returned to pool: {}", shares_to_return);
+    msg!("Shares returned to investor: {}", shares_to_return);
 
     Ok(())
 }
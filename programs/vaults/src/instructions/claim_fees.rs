@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::{
+    FeeLedger, FundState, ProtocolConfig,
+    FUND_SEED, FEE_LEDGER_SEED, VAULT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED,
+};
+use crate::errors::FundError;
+
+/// Withdraw the manager's accrued fees from the fee escrow
+/// Manager only - pays out to any USDC account the manager owns
+/// Never paused: the fees are already earned and held outside the vault
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    pub manager: Signer<'info>,
+
+    #[account(
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump,
+        constraint = fund_state.manager == manager.key() @ FundError::UnauthorizedManager
+    )]
+    pub fund_state: Account<'info, FundState>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Fee escrow (vault authority owned)
+    #[account(
+        mut,
+        constraint = fee_escrow.key() == fund_state.fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub fee_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, fund_state.key().as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    /// Manager's USDC account receiving the fees
+    #[account(
+        mut,
+        constraint = destination.mint == fund_state.usdc_mint @ FundError::InvalidUsdcMint,
+        constraint = destination.owner == manager.key() @ FundError::InvalidTokenAccountOwner
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraw the protocol's accrued fees from a fund's fee escrow
+/// Protocol fee recipient only
+#[derive(Accounts)]
+pub struct ClaimProtocolFees<'info> {
+    pub recipient: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.protocol_fee_recipient == recipient.key() @ FundError::UnauthorizedFeeRecipient
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Account<'info, FundState>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Fee escrow (vault authority owned)
+    #[account(
+        mut,
+        constraint = fee_escrow.key() == fund_state.fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub fee_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, fund_state.key().as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    /// Recipient's USDC account receiving the fees
+    #[account(
+        mut,
+        constraint = destination.mint == fund_state.usdc_mint @ FundError::InvalidUsdcMint,
+        constraint = destination.owner == recipient.key() @ FundError::InvalidTokenAccountOwner
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+    let amount = ctx.accounts.fee_ledger.manager_unclaimed;
+    require!(amount > 0, FundError::NoFeesToClaim);
+
+    let fund = &ctx.accounts.fund_state;
+    let fund_key = fund.key();
+    let seeds = &[
+        VAULT_AUTHORITY_SEED,
+        fund_key.as_ref(),
+        &[fund.vault_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.fee_escrow.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let ledger = &mut ctx.accounts.fee_ledger;
+    ledger.manager_unclaimed = 0;
+
+    msg!("Manager fees claimed: {} USDC", amount);
+    msg!("Manager lifetime fees: {} USDC", ledger.manager_lifetime);
+
+    Ok(())
+}
+
+pub fn handler_claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+    let amount = ctx.accounts.fee_ledger.protocol_unclaimed;
+    require!(amount > 0, FundError::NoFeesToClaim);
+
+    let fund = &ctx.accounts.fund_state;
+    let fund_key = fund.key();
+    let seeds = &[
+        VAULT_AUTHORITY_SEED,
+        fund_key.as_ref(),
+        &[fund.vault_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.fee_escrow.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let ledger = &mut ctx.accounts.fee_ledger;
+    ledger.protocol_unclaimed = 0;
+
+    msg!("Protocol fees claimed for fund {}: {} USDC", fund.fund_id, amount);
+    msg!("Protocol lifetime fees: {} USDC", ledger.protocol_lifetime);
+
+    Ok(())
+}
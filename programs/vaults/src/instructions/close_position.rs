@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{FundState, Position, FUND_SEED, VAULT_AUTHORITY_SEED, POSITION_SEED};
+use crate::errors::FundError;
+
+/// Close a flat position record
+/// Anyone can call once the position tracks no tokens - the rent goes back to
+/// the manager who paid for it. Closing lowers `fund_state.position_count`, so
+/// finalize_close only has to prove the positions that are still on the books.
+///
+/// Only the tracked quantity matters: anyone can send outcome tokens to the
+/// vault ATA, so requiring an empty ATA would let a donation block the close.
+/// Never paused: it only reclaims the rent of an empty record
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, fund_state.key().as_ref(), position.outcome_mint.as_ref()],
+        bump = position.bump,
+        constraint = position.fund == fund_state.key() @ FundError::PositionsNotClosed,
+        constraint = !position.is_open() @ FundError::PositionsNotClosed,
+        close = manager
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// CHECK: Fund manager (receives the rent)
+    #[account(
+        mut,
+        constraint = manager.key() == fund_state.manager @ FundError::UnauthorizedManager
+    )]
+    pub manager: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
+    // Accrue the management fee on every state change
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    let fund = &mut ctx.accounts.fund_state;
+
+    fund.position_count = fund.position_count.checked_sub(1)
+        .ok_or(FundError::MathUnderflow)?;
+
+    msg!("Position closed: {}", ctx.accounts.position.outcome_mint);
+    msg!("Realized PnL: {}", ctx.accounts.position.realized_pnl);
+    msg!("Positions remaining: {}", fund.position_count);
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    FundState, WithdrawalQueue, WithdrawalRequest,
+    FUND_SEED, WITHDRAWAL_QUEUE_SEED, WITHDRAWAL_REQUEST_SEED,
+};
+use crate::errors::FundError;
+
+/// Close a completed or cancelled withdrawal request
+/// Anyone can call once the queue head has passed the request - the rent is
+/// always returned to the investor who paid for it
+/// Never paused: the request is already settled or cancelled
+#[derive(Accounts)]
+#[instruction(sequence: u64)]
+pub struct CloseWithdrawalRequest<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+
+    #[account(
+        seeds = [WITHDRAWAL_QUEUE_SEED, fund_state.key().as_ref()],
+        bump = withdrawal_queue.bump,
+        constraint = sequence < withdrawal_queue.head @ FundError::WithdrawalRequestNotClosable
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+
+    #[account(
+        mut,
+        seeds = [
+            WITHDRAWAL_REQUEST_SEED,
+            fund_state.key().as_ref(),
+            &sequence.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.is_terminal() @ FundError::WithdrawalRequestNotClosable,
+        close = investor
+    )]
+    pub withdrawal_request: Box<Account<'info, WithdrawalRequest>>,
+
+    /// CHECK: Investor who created the request (receives the rent)
+    #[account(
+        mut,
+        constraint = investor.key() == withdrawal_request.investor @ FundError::UnauthorizedInvestor
+    )]
+    pub investor: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CloseWithdrawalRequest>, sequence: u64) -> Result<()> {
+    let request = &ctx.accounts.withdrawal_request;
+
+    msg!("Withdrawal request {} closed", sequence);
+    msg!("Status: {:?}", request.status);
+    msg!("USDC received: {}", request.usdc_received);
+    msg!("Rent returned to: {}", request.investor);
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{FundState, FUND_SEED, VAULT_AUTHORITY_SEED, SHARE_MINT_SEED};
+use crate::errors::FundError;
+
+/// Move minted management fee shares from the fee share escrow to the manager
+/// Every fund-mutating handler accrues the fee and mints the shares into the
+/// escrow (see `fees::MgmtFeeAccounts`); this accrues up to now and hands the
+/// escrow balance to the manager.
+/// Never paused: the shares are already issued, so claiming moves no value
+#[derive(Accounts)]
+pub struct CollectManagementFee<'info> {
+    pub manager: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump,
+        constraint = fund_state.manager == manager.key() @ FundError::UnauthorizedManager
+    )]
+    pub fund_state: Account<'info, FundState>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Share mint
+    #[account(
+        mut,
+        seeds = [SHARE_MINT_SEED, fund_state.key().as_ref()],
+        bump = fund_state.share_mint_bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Manager's share token account
+    #[account(
+        mut,
+        constraint = manager_share_ata.mint == fund_state.share_mint @ FundError::InvalidShareMint,
+        constraint = manager_share_ata.owner == manager.key()
+    )]
+    pub manager_share_ata: Account<'info, TokenAccount>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CollectManagementFee>) -> Result<()> {
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    ctx.accounts.mgmt_fee_escrow.reload()?;
+    let shares = ctx.accounts.mgmt_fee_escrow.amount;
+    require!(shares > 0, FundError::NoManagementFeeToCollect);
+
+    let fund = &ctx.accounts.fund_state;
+    let fund_key = fund.key();
+    let seeds = &[
+        VAULT_AUTHORITY_SEED,
+        fund_key.as_ref(),
+        &[fund.vault_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+            to: ctx.accounts.manager_share_ata.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, shares)?;
+
+    msg!("Management fee collected: {} shares", shares);
+    msg!("Total shares: {}", fund.total_shares);
+
+    Ok(())
+}
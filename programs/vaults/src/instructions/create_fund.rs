@@ -3,8 +3,10 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
 
 use crate::state::{
-    FundState, FundStage, ProtocolConfig,
-    PROTOCOL_CONFIG_SEED, FUND_SEED, VAULT_AUTHORITY_SEED, SHARE_MINT_SEED,
+    FeeLedger, FundState, FundStage, ProtocolConfig, WithdrawalPricing, WithdrawalQueue,
+    PROTOCOL_CONFIG_SEED, FUND_SEED, WITHDRAWAL_QUEUE_SEED, VAULT_AUTHORITY_SEED, SHARE_MINT_SEED,
+    FEE_LEDGER_SEED, FEE_ESCROW_SEED, MGMT_FEE_ESCROW_SEED,
+    MIN_EPOCH_INTERVAL_SECS, MAX_EPOCH_INTERVAL_SECS,
 };
 use crate::errors::FundError;
 
@@ -29,6 +31,16 @@ pub struct CreateFund<'info> {
     )]
     pub fund_state: Box<Account<'info, FundState>>,
 
+    /// FIFO withdrawal queue for the fund
+    #[account(
+        init,
+        payer = manager,
+        space = WithdrawalQueue::LEN,
+        seeds = [WITHDRAWAL_QUEUE_SEED, fund_state.key().as_ref()],
+        bump
+    )]
+    pub withdrawal_queue: Box<Account<'info, WithdrawalQueue>>,
+
     /// USDC mint
     #[account(
         constraint = usdc_mint.key() == protocol_config.usdc_mint @ FundError::InvalidUsdcMint
@@ -40,6 +52,7 @@ pub struct CreateFund<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreateFund>,
     fund_id: u64,
@@ -47,8 +60,15 @@ pub fn handler(
     symbol: String,
     deposit_fee_bps: u16,
     perf_fee_bps: u16,
+    mgmt_fee_bps: u16,
+    early_exit_fee_bps: u16,
+    liquidity_buffer_bps: u16,
+    epoch_interval_secs: i64,
     trading_start_ts: i64,
     trading_end_ts: i64,
+    max_drawdown_bps: u16,
+    withdrawal_pricing: WithdrawalPricing,
+    max_epoch_redemption_bps: u16,
 ) -> Result<()> {
     let protocol_config = &ctx.accounts.protocol_config;
     let clock = Clock::get()?;
@@ -68,6 +88,35 @@ pub fn handler(
         perf_fee_bps <= protocol_config.max_perf_fee_bps,
         FundError::PerfFeeExceedsMax
     );
+    require!(
+        mgmt_fee_bps <= protocol_config.max_mgmt_fee_bps,
+        FundError::MgmtFeeExceedsMax
+    );
+    require!(
+        early_exit_fee_bps <= protocol_config.max_early_exit_fee_bps,
+        FundError::EarlyExitFeeExceedsMax
+    );
+
+    // Validate liquidity profile
+    require!(
+        liquidity_buffer_bps >= protocol_config.min_buffer_bps && liquidity_buffer_bps <= 10_000,
+        FundError::BufferBelowMinimum
+    );
+    let epoch_interval_secs = if epoch_interval_secs == 0 {
+        protocol_config.default_epoch_interval_secs
+    } else {
+        epoch_interval_secs
+    };
+    require!(
+        (MIN_EPOCH_INTERVAL_SECS..=MAX_EPOCH_INTERVAL_SECS).contains(&epoch_interval_secs),
+        FundError::InvalidEpochInterval
+    );
+    
+    // Validate drawdown limit (0 disables the breaker)
+    require!(max_drawdown_bps <= 10_000, FundError::InvalidDrawdownLimit);
+
+    // Validate epoch redemption gate (0 disables the gate)
+    require!(max_epoch_redemption_bps <= 10_000, FundError::InvalidRedemptionGate);
     
     // Validate trading period
     require!(
@@ -99,22 +148,41 @@ pub fn handler(
     
     fund.deposit_fee_bps = deposit_fee_bps;
     fund.perf_fee_bps = perf_fee_bps;
-    fund.early_exit_fee_bps = 500;  // 5% default
-    fund.liquidity_buffer_bps = 1000;  // 10% default
+    fund.mgmt_fee_bps = mgmt_fee_bps;
+    fund.last_mgmt_accrual_ts = clock.unix_timestamp;
+    fund.unminted_mgmt_fee_shares = 0;
+    fund.early_exit_fee_bps = early_exit_fee_bps;
+    fund.liquidity_buffer_bps = liquidity_buffer_bps;
     fund.trading_start_ts = trading_start_ts;
     fund.trading_end_ts = trading_end_ts;
     fund.stage = FundStage::Open;
+    fund.season = 1;
     
     // Withdrawal queue initialization
     fund.pending_withdrawal_shares = 0;
     fund.last_epoch_ts = 0;
-    fund.epoch_interval_secs = 86400; // 24 hours default
-    fund.pending_request_count = 0;
+    fund.epoch_interval_secs = epoch_interval_secs;
+    fund.reserved_request_count = 0;
+    fund.current_epoch = 1;
+    fund.season_start_epoch = 1;
+    fund.withdrawal_pricing = withdrawal_pricing;
+    fund.max_epoch_redemption_bps = max_epoch_redemption_bps;
+    fund.pending_deposit_usdc = 0;
+    fund.pending_withdrawal_locked_usdc = 0;
     
     fund.initial_aum_usdc = 0;
     fund.perf_fee_due_usdc = 0;
     fund.perf_fee_paid = false;
+    fund.high_water_mark = 0;
     fund.total_deposited = 0;
+    fund.position_count = 0;
+    fund.open_position_count = 0;
+    fund.has_mandate = false;
+    fund.max_drawdown_bps = max_drawdown_bps;
+    fund.start_nav_per_share = 0;
+    fund.breaker_tripped = false;
+    fund.paused = 0;
+    fund.admin_paused = 0;
     
     // Store pubkeys that will be derived later
     fund.usdc_mint = ctx.accounts.usdc_mint.key();
@@ -125,15 +193,31 @@ pub fn handler(
     fund.share_mint = Pubkey::default();
     fund.vault_authority = Pubkey::default();
     fund.vault_usdc_ata = Pubkey::default();
-    fund.manager_fee_ata = Pubkey::default();
+    fund.reserved_manager_fee_ata = Pubkey::default();
+    fund.fee_escrow = Pubkey::default();
+    fund.mgmt_fee_escrow = Pubkey::default();
     fund.vault_authority_bump = 0;
     fund.share_mint_bump = 0;
 
+    let queue = &mut ctx.accounts.withdrawal_queue;
+    queue.fund = ctx.accounts.fund_state.key();
+    queue.head = 0;
+    queue.tail = 0;
+    queue.bump = ctx.bumps.withdrawal_queue;
+
+    let fund = &ctx.accounts.fund_state;
     msg!("Fund created: {}", fund_id);
     msg!("Manager: {}", fund.manager);
     msg!("Deposit fee: {} bps", deposit_fee_bps);
     msg!("Performance fee: {} bps", perf_fee_bps);
+    msg!("Management fee: {} bps/year", mgmt_fee_bps);
+    msg!("Early exit fee: {} bps", early_exit_fee_bps);
+    msg!("Liquidity buffer: {} bps", liquidity_buffer_bps);
+    msg!("Epoch interval: {} s", epoch_interval_secs);
     msg!("Trading window: {} to {}", trading_start_ts, trading_end_ts);
+    msg!("Max drawdown: {} bps", max_drawdown_bps);
+    msg!("Withdrawal pricing: {:?}", withdrawal_pricing);
+    msg!("Epoch redemption gate: {} bps", max_epoch_redemption_bps);
 
     Ok(())
 }
@@ -228,14 +312,42 @@ pub struct InitializeVaultAccounts<'info> {
     )]
     pub vault_usdc_ata: Account<'info, TokenAccount>,
 
-    /// Manager's fee receiving USDC account
+    /// Fee escrow holding accrued fees until claimed (owned by vault_authority)
     #[account(
-        init_if_needed,
+        init,
         payer = manager,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = manager,
+        seeds = [FEE_ESCROW_SEED, fund_state.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = vault_authority,
+    )]
+    pub fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
     )]
-    pub manager_fee_ata: Account<'info, TokenAccount>,
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow (owned by vault_authority, claimed by the manager)
+    #[account(
+        init,
+        payer = manager,
+        seeds = [MGMT_FEE_ESCROW_SEED, fund_state.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = vault_authority,
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Fee ledger tracking claimable and lifetime fees
+    #[account(
+        init,
+        payer = manager,
+        space = FeeLedger::LEN,
+        seeds = [FEE_LEDGER_SEED, fund_state.key().as_ref()],
+        bump
+    )]
+    pub fee_ledger: Box<Account<'info, FeeLedger>>,
 
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -246,11 +358,23 @@ pub fn handler_init_vault_accounts(ctx: Context<InitializeVaultAccounts>) -> Res
     let fund = &mut ctx.accounts.fund_state;
     
     fund.vault_usdc_ata = ctx.accounts.vault_usdc_ata.key();
-    fund.manager_fee_ata = ctx.accounts.manager_fee_ata.key();
+    fund.fee_escrow = ctx.accounts.fee_escrow.key();
+    fund.mgmt_fee_escrow = ctx.accounts.mgmt_fee_escrow.key();
+
+    let ledger = &mut ctx.accounts.fee_ledger;
+    ledger.fund = fund.key();
+    ledger.manager_unclaimed = 0;
+    ledger.protocol_unclaimed = 0;
+    ledger.manager_lifetime = 0;
+    ledger.protocol_lifetime = 0;
+    ledger.deposit_fees_lifetime = 0;
+    ledger.perf_fees_lifetime = 0;
+    ledger.bump = ctx.bumps.fee_ledger;
 
     msg!("Vault accounts initialized");
     msg!("Vault USDC ATA: {}", fund.vault_usdc_ata);
-    msg!("Manager fee ATA: {}", fund.manager_fee_ata);
+    msg!("Fee escrow: {}", fund.fee_escrow);
+    msg!("Management fee escrow: {}", fund.mgmt_fee_escrow);
 
     Ok(())
 }
@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo, Transfer};
 
-use crate::state::{FundState, FundStage, FUND_SEED, VAULT_AUTHORITY_SEED, SHARE_MINT_SEED};
+use crate::fees::MgmtFeeAccounts;
+use crate::nav::fund_nav;
+use crate::state::{
+    require_not_paused, FeeLedger, FundState, FundStage, ProtocolConfig,
+    FUND_SEED, FEE_LEDGER_SEED, VAULT_AUTHORITY_SEED, SHARE_MINT_SEED, PROTOCOL_CONFIG_SEED, PAUSE_DEPOSITS,
+};
 use crate::errors::FundError;
 
 #[derive(Accounts)]
@@ -17,6 +22,12 @@ pub struct Deposit<'info> {
     )]
     pub fund_state: Account<'info, FundState>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// CHECK: PDA for vault authority
     #[account(
         seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
@@ -46,12 +57,20 @@ pub struct Deposit<'info> {
     )]
     pub vault_usdc_ata: Account<'info, TokenAccount>,
 
-    /// Manager's fee receiving USDC account
+    /// Fee escrow (vault authority owned, holds fees until claimed)
+    #[account(
+        mut,
+        constraint = fee_escrow.key() == fund_state.fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub fee_escrow: Account<'info, TokenAccount>,
+
+    /// Fee ledger tracking claimable manager and protocol fees
     #[account(
         mut,
-        constraint = manager_fee_ata.key() == fund_state.manager_fee_ata
+        seeds = [FEE_LEDGER_SEED, fund_state.key().as_ref()],
+        bump = fee_ledger.bump
     )]
-    pub manager_fee_ata: Account<'info, TokenAccount>,
+    pub fee_ledger: Account<'info, FeeLedger>,
 
     /// Investor's share token account
     #[account(
@@ -60,10 +79,28 @@ pub struct Deposit<'info> {
     )]
     pub investor_share_ata: Account<'info, TokenAccount>,
 
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
 }
 
 pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_DEPOSITS)?;
+
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
     require!(amount > 0, FundError::ZeroDeposit);
 
     let fund = &mut ctx.accounts.fund_state;
@@ -73,24 +110,35 @@ pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     let net_amount = amount.checked_sub(deposit_fee)
         .ok_or(FundError::MathUnderflow)?;
 
-    // Get current vault balance for share calculation
+    // Get current NAV for share calculation
+    let clock = Clock::get()?;
     let vault_balance = ctx.accounts.vault_usdc_ata.amount;
+    let nav = fund_nav(
+        fund,
+        vault_balance,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+        ctx.accounts.protocol_config.max_price_staleness_secs,
+    )?;
     
     // Calculate shares to mint
-    let shares_to_mint = fund.shares_for_deposit(net_amount, vault_balance);
+    let shares_to_mint = fund.shares_for_deposit(net_amount, nav)?;
 
-    // 1. Transfer deposit fee to manager (if fee > 0)
+    // 1. Move the deposit fee into the fee escrow and credit the ledger (if fee > 0)
     if deposit_fee > 0 {
         let transfer_fee_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
                 from: ctx.accounts.investor_usdc_ata.to_account_info(),
-                to: ctx.accounts.manager_fee_ata.to_account_info(),
+                to: ctx.accounts.fee_escrow.to_account_info(),
                 authority: ctx.accounts.investor.to_account_info(),
             },
         );
         token::transfer(transfer_fee_ctx, deposit_fee)?;
     }
+    let protocol_fee = ctx.accounts.protocol_config.protocol_share(deposit_fee);
+    let manager_fee = deposit_fee - protocol_fee;
+    ctx.accounts.fee_ledger.credit_deposit_fee(deposit_fee, protocol_fee)?;
 
     // 2. Transfer net amount to vault
     let transfer_vault_ctx = CpiContext::new(
@@ -123,13 +171,16 @@ pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     );
     token::mint_to(mint_ctx, shares_to_mint)?;
 
-    // Update fund state
+    // Update fund state - the first shares of an empty fund restart the mark
+    let nav_per_share = fund.nav_per_share(nav);
+    fund.restart_hwm_if_empty(nav_per_share);
     fund.total_shares = fund.total_shares.checked_add(shares_to_mint)
         .ok_or(FundError::MathOverflow)?;
     fund.total_deposited = fund.total_deposited.checked_add(amount)
         .ok_or(FundError::MathOverflow)?;
 
     msg!("Deposit: {} USDC (fee: {}, net: {})", amount, deposit_fee, net_amount);
+    msg!("Fee split: manager {}, protocol {}", manager_fee, protocol_fee);
     msg!("Shares minted: {}", shares_to_mint);
     msg!("Total shares: {}", fund.total_shares);
 
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::state::{FundState, FundStage, FUND_SEED};
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{FundState, FundStage, FUND_SEED, VAULT_AUTHORITY_SEED};
 use crate::errors::FundError;
 
 #[derive(Accounts)]
@@ -14,9 +16,41 @@ pub struct EndTrading<'info> {
         constraint = fund_state.stage == FundStage::Trading @ FundError::InvalidStage
     )]
     pub fund_state: Account<'info, FundState>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
 }
 
 pub fn handler(ctx: Context<EndTrading>) -> Result<()> {
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
     let fund = &mut ctx.accounts.fund_state;
     let clock = Clock::get()?;
 
@@ -1,21 +1,40 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::state::{FundState, FundStage, ProtocolConfig, FUND_SEED, VAULT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED};
+use crate::dflow::{validate_and_execute_bundle, DFlowInstruction};
+use crate::fees::MgmtFeeAccounts;
+use crate::nav::{fund_nav, split_nav_accounts};
+use crate::state::{
+    load_account, AllowedMarket, FundMandate, FundState, FundStage, MarketStatus, Position, ProtocolConfig, Side, Direction,
+    FUND_SEED, VAULT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED, POSITION_SEED, ALLOWED_MARKET_SEED,
+    MANDATE_SEED, MAX_OUTCOME_PRICE, OUTCOME_TOKEN_DECIMALS,
+    require_not_paused, PAUSE_TRADING,
+};
 use crate::errors::FundError;
 
 /// Execute trade instruction
-/// 
-/// This instruction validates and executes DFlow swap transactions.
-/// In MVP, we validate the manager and stage, then accept mock trade data.
-/// 
-/// In production, this would:
-/// 1. Validate the DFlow instruction bundle
-/// 2. Verify all token accounts belong to vault authority
-/// 3. Execute via invoke_signed
+///
+/// Validates and executes a DFlow swap bundle on behalf of the fund:
+/// 0. The market must be listed in the protocol allowlist and within the
+///    fund mandate (if any); buys need an active market, sells may reduce
+///    positions in suspended or resolved markets
+/// 1. Every instruction must target the whitelisted DFlow program
+/// 2. Every SPL token account in the bundle must belong to the vault authority,
+///    and only `vault_usdc_ata` and `outcome_token_ata` may be written
+/// 3. The bundle is executed via invoke_signed with vault authority seeds
+/// 4. Vault balance deltas are checked against the amount, minimum output
+///    and slippage tolerance declared in `TradeParams`
+/// 5. The fund's `Position` for the outcome mint is updated from the deltas
+///
+/// All accounts referenced by the bundle (including the DFlow program) are
+/// passed through `remaining_accounts`. When NAV is needed (drawdown breaker
+/// or mandate exposure limit) the (Position, MarketPrice) pairs of the open
+/// positions lead `remaining_accounts`.
 #[derive(Accounts)]
 pub struct ExecuteTrade<'info> {
     #[account(
+        mut,
         constraint = manager.key() == fund_state.manager @ FundError::UnauthorizedManager
     )]
     pub manager: Signer<'info>,
@@ -34,6 +53,21 @@ pub struct ExecuteTrade<'info> {
     )]
     pub fund_state: Account<'info, FundState>,
 
+    /// Allowlist entry for the traded market
+    #[account(
+        mut,
+        seeds = [ALLOWED_MARKET_SEED, allowed_market.market_ledger.as_ref()],
+        bump = allowed_market.bump
+    )]
+    pub allowed_market: Box<Account<'info, AllowedMarket>>,
+
+    /// Fund mandate (required when `fund_state.has_mandate`)
+    #[account(
+        seeds = [MANDATE_SEED, fund_state.key().as_ref()],
+        bump = mandate.bump
+    )]
+    pub mandate: Option<Box<Account<'info, FundMandate>>>,
+
     /// CHECK: PDA for vault authority
     #[account(
         seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
@@ -48,92 +82,502 @@ pub struct ExecuteTrade<'info> {
     )]
     pub vault_usdc_ata: Account<'info, TokenAccount>,
 
-    // NOTE: In production, additional accounts would be passed for:
-    // - DFlow program
-    // - Outcome token mints
-    // - Outcome token ATAs
-    // - Any other required accounts from DFlow instruction bundle
+    /// Mint of the traded outcome token (prices assume OUTCOME_TOKEN_DECIMALS)
+    #[account(
+        constraint = outcome_mint.decimals == OUTCOME_TOKEN_DECIMALS @ FundError::InvalidOutcomeMint
+    )]
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    /// Vault's outcome token ATA for the traded side
+    #[account(
+        mut,
+        constraint = outcome_token_ata.mint == outcome_mint.key() @ FundError::OutcomeMintMismatch,
+        constraint = outcome_token_ata.owner == vault_authority.key() @ FundError::InvalidTokenAccountOwner,
+        constraint = outcome_token_ata.key() == get_associated_token_address(
+            &vault_authority.key(),
+            &outcome_token_ata.mint
+        ) @ FundError::InvalidTokenAccountOwner,
+        constraint = outcome_token_ata.mint != fund_state.usdc_mint @ FundError::InvalidTradeInstruction
+    )]
+    pub outcome_token_ata: Account<'info, TokenAccount>,
+
+    /// Position record for the traded outcome mint
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = Position::LEN,
+        seeds = [POSITION_SEED, fund_state.key().as_ref(), outcome_token_ata.mint.as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
 }
 
-/// Trade parameters - for MVP, we log and validate but don't execute real trades
+/// Trade parameters - describe the trade intent of the DFlow bundle
+/// Markets are referenced by their DFlow ledger and outcome mints
+/// (the `accounts` block of DFlow market data). Unknown side or direction
+/// discriminants fail instruction deserialization.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct TradeParams {
-    /// Market identifier
-    pub market_id: String,
-    /// YES or NO
-    pub side: String,
-    /// BUY or SELL
-    pub direction: String,
-    /// Amount in USDC (scaled)
+    /// DFlow market ledger account
+    pub market_ledger: Pubkey,
+    /// YES outcome token mint
+    pub yes_mint: Pubkey,
+    /// NO outcome token mint
+    pub no_mint: Pubkey,
+    /// Outcome side being traded
+    pub side: Side,
+    /// Buy or sell the outcome token
+    pub direction: Direction,
+    /// Maximum input amount: USDC spent on BUY, outcome tokens sold on SELL
     pub amount: u64,
+    /// Minimum output amount: outcome tokens received on BUY, USDC received on SELL
+    /// (must be non-zero - it is the hard floor that does not trust the quote)
+    pub min_out: u64,
+    /// Quoted output amount for `amount` (reference for slippage, at least `min_out`)
+    pub expected_out: u64,
     /// Slippage tolerance in basis points
     pub slippage_bps: u16,
+    /// Optional limit price in micro-USDC per outcome token
+    /// (maximum on Buy, minimum on Sell)
+    pub limit_price: Option<u64>,
 }
 
-pub fn handler(ctx: Context<ExecuteTrade>, params: TradeParams) -> Result<()> {
+impl TradeParams {
+    /// Mint of the outcome token being traded
+    pub fn outcome_mint(&self) -> Pubkey {
+        if self.side == Side::Yes {
+            self.yes_mint
+        } else {
+            self.no_mint
+        }
+    }
+
+    pub fn is_buy(&self) -> bool {
+        self.direction == Direction::Buy
+    }
+
+    /// Validate parameters before executing the trade
+    pub fn validate(&self) -> Result<()> {
+        require!(self.amount > 0, FundError::InvalidTradeParams);
+        require!(self.min_out > 0, FundError::InvalidTradeParams);
+        require!(self.expected_out >= self.min_out, FundError::InvalidTradeParams);
+        require!(self.slippage_bps <= 10_000, FundError::InvalidTradeParams);
+        require!(self.yes_mint != self.no_mint, FundError::InvalidTradeParams);
+        if let Some(limit_price) = self.limit_price {
+            require!(
+                limit_price > 0 && limit_price <= MAX_OUTCOME_PRICE,
+                FundError::InvalidTradeParams
+            );
+        }
+        Ok(())
+    }
+
+    /// Check the executed price against the limit price (if any)
+    /// Price is micro-USDC per whole outcome token
+    pub fn check_limit_price(&self, usdc_amount: u64, outcome_amount: u64) -> Result<()> {
+        let Some(limit_price) = self.limit_price else {
+            return Ok(());
+        };
+        require!(outcome_amount > 0, FundError::InsufficientTradeOutput);
+
+        let price = (usdc_amount as u128) * (MAX_OUTCOME_PRICE as u128) / (outcome_amount as u128);
+        if self.is_buy() {
+            require!(price <= limit_price as u128, FundError::LimitPriceExceeded);
+        } else {
+            require!(price >= limit_price as u128, FundError::LimitPriceExceeded);
+        }
+        Ok(())
+    }
+}
+
+/// Vault balances captured around the trade CPI
+#[derive(Clone, Copy, Debug)]
+pub struct TradeSnapshot {
+    pub usdc: u64,
+    pub outcome: u64,
+}
+
+/// Check post-trade balance deltas against the trade parameters
+/// Returns (input spent, output received)
+pub fn enforce_trade_deltas(
+    params: &TradeParams,
+    before: TradeSnapshot,
+    after: TradeSnapshot,
+) -> Result<(u64, u64)> {
+    let (in_before, in_after, out_before, out_after) = if params.is_buy() {
+        (before.usdc, after.usdc, before.outcome, after.outcome)
+    } else {
+        (before.outcome, after.outcome, before.usdc, after.usdc)
+    };
+
+    // Input side may only decrease, output side may only increase
+    let input_spent = in_before.checked_sub(in_after)
+        .ok_or(FundError::InvalidTradeInstruction)?;
+    let output_received = out_after.checked_sub(out_before)
+        .ok_or(FundError::InsufficientTradeOutput)?;
+
+    require!(input_spent <= params.amount, FundError::TradeAmountExceeded);
+    require!(output_received >= params.min_out, FundError::InsufficientTradeOutput);
+
+    // Realized rate (output / input) must be within slippage of the quoted rate:
+    // received / spent >= expected_out / amount * (1 - slippage)
+    let realized = (output_received as u128)
+        .checked_mul(params.amount as u128)
+        .and_then(|v| v.checked_mul(10_000))
+        .ok_or(FundError::MathOverflow)?;
+    let min_allowed = (params.expected_out as u128)
+        .checked_mul(input_spent as u128)
+        .and_then(|v| v.checked_mul(10_000 - params.slippage_bps as u128))
+        .ok_or(FundError::MathOverflow)?;
+    require!(realized >= min_allowed, FundError::SlippageExceeded);
+
+    if params.is_buy() {
+        params.check_limit_price(input_spent, output_received)?;
+    } else {
+        params.check_limit_price(output_received, input_spent)?;
+    }
+
+    Ok((input_spent, output_received))
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteTrade<'info>>,
+    params: TradeParams,
+    dflow_instructions: Vec<DFlowInstruction>,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_TRADING)?;
+
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
     let fund = &ctx.accounts.fund_state;
-    let _protocol_config = &ctx.accounts.protocol_config;
+    let protocol_config = &ctx.accounts.protocol_config;
+
+    params.validate()?;
+
+    // Market must be listed and match the declared outcome mints. Only buys
+    // need an active market - sells reduce an existing position.
+    let market = &ctx.accounts.allowed_market;
+    require!(
+        market.market_ledger == params.market_ledger
+            && market.yes_mint == params.yes_mint
+            && market.no_mint == params.no_mint,
+        FundError::MarketNotAllowed
+    );
+    require!(
+        market.status == MarketStatus::Active || !params.is_buy(),
+        FundError::MarketSuspended
+    );
+
+    // Mandate: market scope
+    let mut max_position_bps = 0;
+    if fund.has_mandate {
+        let mandate = ctx.accounts.mandate.as_ref().ok_or(FundError::MandateMissing)?;
+        require!(
+            mandate.allows_market(market.category, &params.outcome_mint()),
+            FundError::MandateViolation
+        );
+        max_position_bps = mandate.max_position_bps;
+    }
+
+    // Pre-trade NAV for the drawdown breaker and the mandate exposure limit
+    let mut max_position_usdc: Option<u64> = None;
+    let mut other_side_cost: u64 = 0;
+    let needs_nav = fund.max_drawdown_bps > 0 || (max_position_bps > 0 && params.is_buy());
+    if needs_nav {
+        let clock = Clock::get()?;
+        let (nav_accounts, _) = split_nav_accounts(fund, ctx.remaining_accounts)?;
+        let nav = fund_nav(
+            fund,
+            ctx.accounts.vault_usdc_ata.amount,
+            nav_accounts,
+            clock.unix_timestamp,
+            ctx.accounts.protocol_config.max_price_staleness_secs,
+        )?;
+
+        // Below the drawdown floor only position-reducing trades are allowed
+        if fund.drawdown_breached(nav) {
+            require!(!params.is_buy(), FundError::DrawdownBreached);
+            msg!("Drawdown breaker active: position-reducing trades only");
+        }
 
-    // Validate amount
-    require!(params.amount > 0, FundError::ZeroDeposit);
+        if max_position_bps > 0 {
+            max_position_usdc = Some(
+                ((nav as u128) * (max_position_bps as u128) / 10_000) as u64
+            );
 
-    // In MVP mode with mock data, we just log the trade intent
-    // In production, we would:
-    // 1. Deserialize the DFlow instruction bundle
-    // 2. Validate each instruction's program_id against allowed_dflow_program
-    // 3. Validate each token account is owned by vault_authority
-    // 4. Execute each instruction via invoke_signed
+            // The exposure limit covers the market, so the other side counts too
+            // (every open position is among the NAV accounts)
+            for pair in nav_accounts.chunks(2) {
+                let held: Position = load_account(&pair[0])?;
+                if held.market_ledger == params.market_ledger && held.outcome_mint != params.outcome_mint() {
+                    other_side_cost = other_side_cost.checked_add(held.cost_basis_usdc)
+                        .ok_or(FundError::MathOverflow)?;
+                }
+            }
+        }
+    }
+    require_keys_eq!(
+        ctx.accounts.outcome_token_ata.mint,
+        params.outcome_mint(),
+        FundError::OutcomeMintMismatch
+    );
 
-    msg!("=== TRADE EXECUTION (MVP MODE) ===");
+    msg!("=== TRADE EXECUTION ===");
     msg!("Fund: {}", fund.fund_id);
-    msg!("Market: {}", params.market_id);
-    msg!("Side: {}", params.side);
-    msg!("Direction: {}", params.direction);
+    msg!("Market: {}", params.market_ledger);
+    msg!("Side: {:?}", params.side);
+    msg!("Direction: {:?}", params.direction);
     msg!("Amount: {}", params.amount);
+    msg!("Min out: {}", params.min_out);
     msg!("Slippage: {} bps", params.slippage_bps);
-    msg!("NOTE: Real DFlow execution pending API integration");
 
-    // In production, this is where we would call:
-    // validate_and_execute_dflow_bundle(ctx, dflow_instructions)?;
+    let before = TradeSnapshot {
+        usdc: ctx.accounts.vault_usdc_ata.amount,
+        outcome: ctx.accounts.outcome_token_ata.amount,
+    };
+
+    // Accounts available to the bundle: remaining_accounts plus the vault accounts
+    let mut account_infos = ctx.remaining_accounts.to_vec();
+    account_infos.push(ctx.accounts.vault_authority.to_account_info());
+    account_infos.push(ctx.accounts.vault_usdc_ata.to_account_info());
+    account_infos.push(ctx.accounts.outcome_token_ata.to_account_info());
+
+    let fund_key = fund.key();
+    let seeds = &[
+        VAULT_AUTHORITY_SEED,
+        fund_key.as_ref(),
+        &[fund.vault_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    validate_and_execute_bundle(
+        &dflow_instructions,
+        &protocol_config.allowed_dflow_program,
+        &ctx.accounts.vault_authority.to_account_info(),
+        &[ctx.accounts.vault_usdc_ata.key(), ctx.accounts.outcome_token_ata.key()],
+        &account_infos,
+        signer_seeds,
+    )?;
+
+    // Snapshot balances after the CPI and enforce the trade bounds
+    ctx.accounts.vault_usdc_ata.reload()?;
+    ctx.accounts.outcome_token_ata.reload()?;
+    let after = TradeSnapshot {
+        usdc: ctx.accounts.vault_usdc_ata.amount,
+        outcome: ctx.accounts.outcome_token_ata.amount,
+    };
+
+    let (input_spent, output_received) = enforce_trade_deltas(&params, before, after)?;
+
+    msg!("Input spent: {}", input_spent);
+    msg!("Output received: {}", output_received);
+
+    // Update the position registry
+    let position = &mut ctx.accounts.position;
+    if position.fund == Pubkey::default() {
+        position.fund = fund_key;
+        position.market_ledger = params.market_ledger;
+        position.outcome_mint = params.outcome_mint();
+        position.side = params.side;
+        position.quantity = 0;
+        position.cost_basis_usdc = 0;
+        position.realized_pnl = 0;
+        position.bump = ctx.bumps.position;
+
+        let fund = &mut ctx.accounts.fund_state;
+        fund.position_count = fund.position_count.checked_add(1)
+            .ok_or(FundError::MathOverflow)?;
+    }
+    require_keys_eq!(
+        position.market_ledger,
+        params.market_ledger,
+        FundError::PositionMarketMismatch
+    );
+
+    let was_open = position.is_open();
+    let untracked = position.sync_balance(before.outcome);
+    if untracked > 0 {
+        msg!("Untracked outcome tokens added to position: {}", untracked);
+    }
+    if params.is_buy() {
+        position.apply_buy(output_received, input_spent)?;
+    } else {
+        position.apply_sell(input_spent, output_received)?;
+    }
+    let is_open = position.is_open();
+
+    let fund = &mut ctx.accounts.fund_state;
+    let market = &mut ctx.accounts.allowed_market;
+    if !was_open && is_open {
+        fund.open_position_count = fund.open_position_count.checked_add(1)
+            .ok_or(FundError::MathOverflow)?;
+        market.open_positions = market.open_positions.checked_add(1)
+            .ok_or(FundError::MathOverflow)?;
+    } else if was_open && !is_open {
+        fund.open_position_count = fund.open_position_count.checked_sub(1)
+            .ok_or(FundError::MathUnderflow)?;
+        market.open_positions = market.open_positions.checked_sub(1)
+            .ok_or(FundError::MathUnderflow)?;
+    }
+
+    // Mandate: post-trade market exposure and concurrent position limits
+    if let Some(max_position_usdc) = max_position_usdc {
+        let market_cost = position.cost_basis_usdc.checked_add(other_side_cost)
+            .ok_or(FundError::MathOverflow)?;
+        require!(market_cost <= max_position_usdc, FundError::MandateViolation);
+    }
+    if let Some(mandate) = ctx.accounts.mandate.as_ref() {
+        if mandate.max_open_positions > 0 && params.is_buy() {
+            require!(
+                fund.open_position_count <= mandate.max_open_positions,
+                FundError::MandateViolation
+            );
+        }
+    }
+
+    msg!("Position quantity: {}", position.quantity);
+    msg!("Average cost: {}", position.average_cost());
+    msg!("Open positions: {}", fund.open_position_count);
 
     Ok(())
 }
 
-// Production implementation would include:
-// 
-// fn validate_and_execute_dflow_bundle<'info>(
-//     ctx: Context<ExecuteTrade>,
-//     instructions: Vec<DFlowInstruction>,
-// ) -> Result<()> {
-//     let protocol_config = &ctx.accounts.protocol_config;
-//     let fund = &ctx.accounts.fund_state;
-//     let vault_authority = &ctx.accounts.vault_authority;
-//     
-//     for ix in instructions {
-//         // Validate program ID is whitelisted
-//         require!(
-//             ix.program_id == protocol_config.allowed_dflow_program,
-//             FundError::InvalidDFlowProgram
-//         );
-//         
-//         // Validate all SPL token accounts are vault-owned
-//         for account in &ix.accounts {
-//             if is_token_account(account) {
-//                 require!(
-//                     account.owner == vault_authority.key(),
-//                     FundError::InvalidTokenAccountOwner
-//                 );
-//             }
-//         }
-//         
-//         // Execute via CPI
-//         let seeds = &[
-//             VAULT_AUTHORITY_SEED,
-//             fund.key().as_ref(),
-//             &[fund.vault_authority_bump],
-//         ];
-//         invoke_signed(&ix.to_instruction(), accounts, &[seeds])?;
-//     }
-//     
-//     Ok(())
-// }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buy_params() -> TradeParams {
+        TradeParams {
+            market_ledger: Pubkey::default(),
+            yes_mint: Pubkey::new_unique(),
+            no_mint: Pubkey::new_unique(),
+            side: Side::Yes,
+            direction: Direction::Buy,
+            amount: 1_000_000,
+            min_out: 1_900_000,
+            expected_out: 2_000_000,
+            slippage_bps: 100,
+            limit_price: None,
+        }
+    }
+
+    fn sell_params() -> TradeParams {
+        TradeParams {
+            direction: Direction::Sell,
+            amount: 2_000_000,
+            min_out: 900_000,
+            expected_out: 1_000_000,
+            slippage_bps: 1_000,
+            limit_price: Some(480_000),
+            ..buy_params()
+        }
+    }
+
+    fn snapshot(usdc: u64, outcome: u64) -> TradeSnapshot {
+        TradeSnapshot { usdc, outcome }
+    }
+
+    #[test]
+    fn unknown_side_or_direction_fails_to_deserialize() {
+        let bytes = buy_params().try_to_vec().unwrap();
+        assert!(TradeParams::try_from_slice(&bytes).is_ok());
+
+        // side follows the three pubkeys, direction follows side
+        for offset in [96, 97] {
+            let mut bad = bytes.clone();
+            bad[offset] = 2;
+            assert!(TradeParams::try_from_slice(&bad).is_err());
+        }
+    }
+
+    #[test]
+    fn buy_at_quote_passes() {
+        let deltas = enforce_trade_deltas(&buy_params(), snapshot(5_000_000, 0), snapshot(4_000_000, 2_000_000));
+        assert_eq!(deltas.unwrap(), (1_000_000, 2_000_000));
+    }
+
+    #[test]
+    fn buy_within_slippage_passes() {
+        let deltas = enforce_trade_deltas(&buy_params(), snapshot(5_000_000, 0), snapshot(4_000_000, 1_985_000));
+        assert_eq!(deltas.unwrap(), (1_000_000, 1_985_000));
+    }
+
+    #[test]
+    fn buy_beyond_slippage_fails() {
+        let err = enforce_trade_deltas(&buy_params(), snapshot(5_000_000, 0), snapshot(4_000_000, 1_950_000));
+        assert_eq!(err.unwrap_err(), FundError::SlippageExceeded.into());
+    }
+
+    #[test]
+    fn buy_below_min_out_fails() {
+        let err = enforce_trade_deltas(&buy_params(), snapshot(5_000_000, 0), snapshot(4_000_000, 1_800_000));
+        assert_eq!(err.unwrap_err(), FundError::InsufficientTradeOutput.into());
+    }
+
+    #[test]
+    fn overspending_input_fails() {
+        let err = enforce_trade_deltas(&buy_params(), snapshot(5_000_000, 0), snapshot(3_999_999, 2_000_000));
+        assert_eq!(err.unwrap_err(), FundError::TradeAmountExceeded.into());
+    }
+
+    #[test]
+    fn input_balance_increase_fails() {
+        let err = enforce_trade_deltas(&buy_params(), snapshot(5_000_000, 0), snapshot(5_000_001, 2_000_000));
+        assert_eq!(err.unwrap_err(), FundError::InvalidTradeInstruction.into());
+    }
+
+    #[test]
+    fn output_balance_decrease_fails() {
+        let err = enforce_trade_deltas(&buy_params(), snapshot(5_000_000, 10), snapshot(4_000_000, 5));
+        assert_eq!(err.unwrap_err(), FundError::InsufficientTradeOutput.into());
+    }
+
+    #[test]
+    fn sell_above_limit_price_passes() {
+        let deltas = enforce_trade_deltas(&sell_params(), snapshot(0, 2_000_000), snapshot(1_000_000, 0));
+        assert_eq!(deltas.unwrap(), (2_000_000, 1_000_000));
+    }
+
+    #[test]
+    fn sell_below_limit_price_fails() {
+        let err = enforce_trade_deltas(&sell_params(), snapshot(0, 2_000_000), snapshot(950_000, 0));
+        assert_eq!(err.unwrap_err(), FundError::LimitPriceExceeded.into());
+    }
+
+    #[test]
+    fn oversized_amounts_fail_without_overflow() {
+        let params = TradeParams {
+            amount: u64::MAX,
+            min_out: 1,
+            expected_out: u64::MAX,
+            ..buy_params()
+        };
+        let err = enforce_trade_deltas(&params, snapshot(u64::MAX, 0), snapshot(0, u64::MAX));
+        assert_eq!(err.unwrap_err(), FundError::MathOverflow.into());
+    }
+}
@@ -1,11 +1,21 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::state::{FundState, FundStage, FUND_SEED};
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{FundState, FundStage, Position, SeasonSummary, FUND_SEED, VAULT_AUTHORITY_SEED, SEASON_SEED};
 use crate::errors::FundError;
 
+/// Finalize a fund once all positions are closed
+///
+/// `remaining_accounts` must contain every Position the fund still has on the
+/// books (`fund_state.position_count`), and every one must be flat. Flat
+/// positions closed with close_position drop out of the list. Outcome tokens
+/// donated to a vault ATA are not tracked, so they cannot block the close;
+/// sells and redemptions pick them up while the position is on the books.
 #[derive(Accounts)]
 pub struct FinalizeClose<'info> {
+    /// Caller (pays for the SeasonSummary)
+    #[account(mut)]
     pub caller: Signer<'info>,
 
     #[account(
@@ -22,41 +32,120 @@ pub struct FinalizeClose<'info> {
     )]
     pub vault_usdc_ata: Account<'info, TokenAccount>,
 
-    // NOTE: In production, we would also verify that no outcome token ATAs
-    // have non-zero balances. For MVP, we trust that the manager has closed
-    // all positions and the vault only holds USDC.
+    /// On-chain record of the season being closed
+    #[account(
+        init,
+        payer = caller,
+        space = SeasonSummary::LEN,
+        seeds = [SEASON_SEED, fund_state.key().as_ref(), &fund_state.season.to_le_bytes()],
+        bump
+    )]
+    pub season_summary: Box<Account<'info, SeasonSummary>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<FinalizeClose>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeClose<'info>>) -> Result<()> {
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    verify_positions_closed(&ctx.accounts.fund_state, ctx.remaining_accounts)?;
+
     let fund = &mut ctx.accounts.fund_state;
     let vault_balance = ctx.accounts.vault_usdc_ata.amount;
 
-    // In production, verify vault holds only USDC (no outcome tokens)
-    // For MVP, we proceed with the assumption that positions are closed
-
-    // Calculate performance fee
+    // Crystallize the performance fee above the high-water mark
+    // (positions are closed, so vault USDC less the unpaid fee is the NAV)
     let initial_aum = fund.initial_aum_usdc;
     let final_balance = vault_balance;
-    
-    let profit = if final_balance > initial_aum {
-        final_balance - initial_aum
-    } else {
-        0
-    };
-
-    let perf_fee = fund.calculate_perf_fee(profit);
-    fund.perf_fee_due_usdc = perf_fee;
-    fund.perf_fee_paid = false;
+    let nav = final_balance.saturating_sub(fund.outstanding_perf_fee());
+    let (perf_fee, nav_after_fee) = fund.crystallize_perf_fee(nav)?;
+    let season_perf_fee = fund.outstanding_perf_fee();
 
     // Transition to Closed stage
     fund.stage = FundStage::Closed;
 
-    msg!("=== FUND FINALIZED ===");
+    // Record the season
+    let clock = Clock::get()?;
+    let summary = &mut ctx.accounts.season_summary;
+    summary.fund = fund.key();
+    summary.season = fund.season;
+    summary.trading_start_ts = fund.trading_start_ts;
+    summary.trading_end_ts = fund.trading_end_ts;
+    summary.closed_at = clock.unix_timestamp;
+    summary.initial_aum_usdc = initial_aum;
+    summary.final_nav_usdc = final_balance;
+    summary.total_shares = fund.total_shares;
+    summary.nav_per_share = fund.nav_per_share(nav_after_fee);
+    summary.high_water_mark = fund.high_water_mark;
+    summary.perf_fee_usdc = season_perf_fee;
+    summary.breaker_tripped = fund.breaker_tripped;
+    summary.bump = ctx.bumps.season_summary;
+
+    msg!("=== FUND FINALIZED (season {}) ===", fund.season);
     msg!("Initial AUM: {} USDC", initial_aum);
     msg!("Final Balance: {} USDC", final_balance);
-    msg!("Profit: {} USDC", profit);
-    msg!("Performance Fee Due: {} USDC", perf_fee);
+    msg!("Performance Fee Crystallized: {} USDC", perf_fee);
+    msg!("Performance Fee Due: {} USDC", season_perf_fee);
+    msg!("High-water mark: {}", fund.high_water_mark);
     msg!("Investors can now redeem shares");
 
     Ok(())
 }
+
+/// Check that every position of the fund is flat
+fn verify_positions_closed<'info>(
+    fund: &Account<'info, FundState>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    require!(fund.open_position_count == 0, FundError::PositionsNotClosed);
+    require!(
+        remaining_accounts.len() == fund.position_count as usize,
+        FundError::PositionsNotClosed
+    );
+
+    let fund_key = fund.key();
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(fund.position_count as usize);
+
+    for info in remaining_accounts {
+        let position = Account::<Position>::try_from(info)?;
+        require_keys_eq!(position.fund, fund_key, FundError::PositionsNotClosed);
+        require!(!seen.contains(&position.key()), FundError::PositionsNotClosed);
+        seen.push(position.key());
+
+        require!(position.quantity == 0, FundError::PositionsNotClosed);
+    }
+
+    msg!("Verified {} position(s) closed", seen.len());
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{ProtocolConfig, PROTOCOL_CONFIG_SEED};
-use crate::errors::FundError;
+use crate::state::{
+    ProtocolConfig, PROTOCOL_CONFIG_SEED, DEFAULT_MAX_MGMT_FEE_BPS, DEFAULT_EPOCH_INTERVAL_SECS,
+    DEFAULT_PRICE_STALENESS_SECS,
+};
 
 #[derive(Accounts)]
 pub struct InitializeProtocol<'info> {
@@ -26,21 +28,45 @@ pub struct InitializeProtocol<'info> {
 pub fn handler(
     ctx: Context<InitializeProtocol>,
     allowed_dflow_program: Pubkey,
+    oracle_authority: Pubkey,
+    max_early_exit_fee_bps: u16,
+    min_buffer_bps: u16,
+    default_epoch_interval_secs: i64,
 ) -> Result<()> {
     let config = &mut ctx.accounts.protocol_config;
     
     config.admin = ctx.accounts.admin.key();
     config.max_deposit_fee_bps = 300;  // 3%
     config.max_perf_fee_bps = 3000;    // 30%
+    config.max_mgmt_fee_bps = DEFAULT_MAX_MGMT_FEE_BPS;
+    config.max_early_exit_fee_bps = max_early_exit_fee_bps;
+    config.min_buffer_bps = min_buffer_bps;
+    config.default_epoch_interval_secs = if default_epoch_interval_secs == 0 {
+        DEFAULT_EPOCH_INTERVAL_SECS
+    } else {
+        default_epoch_interval_secs
+    };
+    config.max_price_staleness_secs = DEFAULT_PRICE_STALENESS_SECS;
+    config.validate_risk_bounds()?;
     config.allowed_dflow_program = allowed_dflow_program;
     config.usdc_mint = ctx.accounts.usdc_mint.key();
     config.protocol_fee_recipient = ctx.accounts.admin.key(); // Default to admin
+    config.protocol_fee_bps = 0;
+    config.oracle_authority = oracle_authority;
+    config.pending_admin = Pubkey::default();
+    config.paused = 0;
     config.bump = ctx.bumps.protocol_config;
 
     msg!("Protocol config initialized");
     msg!("Admin: {}", config.admin);
     msg!("Allowed DFlow program: {}", config.allowed_dflow_program);
     msg!("USDC mint: {}", config.usdc_mint);
+    msg!("Oracle authority: {}", config.oracle_authority);
+    msg!("Max management fee: {} bps", config.max_mgmt_fee_bps);
+    msg!("Max early exit fee: {} bps", config.max_early_exit_fee_bps);
+    msg!("Min liquidity buffer: {} bps", config.min_buffer_bps);
+    msg!("Default epoch interval: {} s", config.default_epoch_interval_secs);
+    msg!("Max price staleness: {} s", config.max_price_staleness_secs);
 
     Ok(())
 }
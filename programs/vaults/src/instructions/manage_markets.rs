@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    AllowedMarket, MarketCategory, MarketStatus, ProtocolConfig, Side,
+    PROTOCOL_CONFIG_SEED, ALLOWED_MARKET_SEED,
+};
+use crate::errors::FundError;
+
+/// List a market in the protocol allowlist
+/// Admin only - funds can only trade listed, active markets
+/// Allowlist changes are admin only and never paused, so a market can always
+/// be suspended while trading is paused
+#[derive(Accounts)]
+#[instruction(market_ledger: Pubkey)]
+pub struct AddMarket<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == protocol_config.admin @ FundError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = AllowedMarket::LEN,
+        seeds = [ALLOWED_MARKET_SEED, market_ledger.as_ref()],
+        bump
+    )]
+    pub allowed_market: Account<'info, AllowedMarket>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler_add_market(
+    ctx: Context<AddMarket>,
+    market_ledger: Pubkey,
+    yes_mint: Pubkey,
+    no_mint: Pubkey,
+    category: MarketCategory,
+) -> Result<()> {
+    require!(yes_mint != no_mint, FundError::InvalidTradeParams);
+
+    let clock = Clock::get()?;
+    let market = &mut ctx.accounts.allowed_market;
+
+    market.market_ledger = market_ledger;
+    market.yes_mint = yes_mint;
+    market.no_mint = no_mint;
+    market.category = category;
+    market.status = MarketStatus::Active;
+    market.listed_at = clock.unix_timestamp;
+    market.bump = ctx.bumps.allowed_market;
+    market.winning_mint = Pubkey::default();
+    market.open_positions = 0;
+
+    msg!("Market listed: {}", market_ledger);
+    msg!("YES mint: {}", yes_mint);
+    msg!("NO mint: {}", no_mint);
+    msg!("Category: {:?}", category);
+
+    Ok(())
+}
+
+/// Suspend or reactivate a listed market
+/// Admin only - suspended markets only accept position-reducing sells,
+/// resolution is final
+#[derive(Accounts)]
+pub struct SetMarketStatus<'info> {
+    #[account(
+        constraint = admin.key() == protocol_config.admin @ FundError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [ALLOWED_MARKET_SEED, allowed_market.market_ledger.as_ref()],
+        bump = allowed_market.bump
+    )]
+    pub allowed_market: Account<'info, AllowedMarket>,
+}
+
+pub fn handler_set_market_status(ctx: Context<SetMarketStatus>, status: MarketStatus) -> Result<()> {
+    let market = &mut ctx.accounts.allowed_market;
+    require!(market.status != MarketStatus::Resolved, FundError::MarketAlreadyResolved);
+    // Resolution records the winning mint, see resolve_market
+    require!(status != MarketStatus::Resolved, FundError::MarketNotResolved);
+    market.status = status;
+
+    msg!("Market {} status: {:?}", market.market_ledger, status);
+
+    Ok(())
+}
+
+/// Mark a listed market as resolved with its winning side
+/// Admin only - enables redeem_outcome, which pays the winning mint at par
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(
+        constraint = admin.key() == protocol_config.admin @ FundError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [ALLOWED_MARKET_SEED, allowed_market.market_ledger.as_ref()],
+        bump = allowed_market.bump,
+        constraint = allowed_market.status != MarketStatus::Resolved @ FundError::MarketAlreadyResolved
+    )]
+    pub allowed_market: Account<'info, AllowedMarket>,
+}
+
+pub fn handler_resolve_market(ctx: Context<ResolveMarket>, winning_side: u8) -> Result<()> {
+    let market = &mut ctx.accounts.allowed_market;
+    let winning_mint = match Side::try_from(winning_side)? {
+        Side::Yes => market.yes_mint,
+        Side::No => market.no_mint,
+    };
+
+    market.status = MarketStatus::Resolved;
+    market.winning_mint = winning_mint;
+
+    msg!("Market resolved: {}", market.market_ledger);
+    msg!("Winning mint: {}", winning_mint);
+
+    Ok(())
+}
+
+/// Delist a market and reclaim its rent
+/// Admin only - refused while any fund holds a position in the market, so
+/// holders can always sell or redeem (suspend the market first)
+#[derive(Accounts)]
+pub struct RemoveMarket<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == protocol_config.admin @ FundError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [ALLOWED_MARKET_SEED, allowed_market.market_ledger.as_ref()],
+        bump = allowed_market.bump,
+        constraint = allowed_market.open_positions == 0 @ FundError::MarketHasOpenPositions,
+        close = admin
+    )]
+    pub allowed_market: Account<'info, AllowedMarket>,
+}
+
+pub fn handler_remove_market(ctx: Context<RemoveMarket>) -> Result<()> {
+    msg!("Market delisted: {}", ctx.accounts.allowed_market.market_ledger);
+
+    Ok(())
+}
@@ -1,27 +1,61 @@
+// Every instruction module exposes its own `handler`; callers always use the
+// fully qualified path, the glob re-exports only serve Anchor's generated code.
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_protocol;
+pub mod protocol_admin;
+pub mod pause;
 pub mod create_fund;
 pub mod deposit;
+pub mod request_deposit;
+pub mod cancel_deposit;
+pub mod process_deposits;
 pub mod withdraw_open;
 pub mod withdraw_early;
 pub mod request_withdrawal;
 pub mod cancel_withdrawal;
+pub mod close_withdrawal_request;
 pub mod process_epoch;
 pub mod start_trading;
 pub mod execute_trade;
 pub mod end_trading;
 pub mod finalize_close;
+pub mod close_position;
 pub mod redeem;
+pub mod redeem_outcome;
+pub mod roll_season;
+pub mod update_market_price;
+pub mod manage_markets;
+pub mod set_mandate;
+pub mod trip_breaker;
+pub mod collect_management_fee;
+pub mod claim_fees;
 
 pub use initialize_protocol::*;
+pub use protocol_admin::*;
+pub use pause::*;
 pub use create_fund::*;
 pub use deposit::*;
+pub use request_deposit::*;
+pub use cancel_deposit::*;
+pub use process_deposits::*;
 pub use withdraw_open::*;
 pub use withdraw_early::*;
 pub use request_withdrawal::*;
 pub use cancel_withdrawal::*;
+pub use close_withdrawal_request::*;
 pub use process_epoch::*;
 pub use start_trading::*;
 pub use execute_trade::*;
 pub use end_trading::*;
 pub use finalize_close::*;
+pub use close_position::*;
 pub use redeem::*;
+pub use redeem_outcome::*;
+pub use roll_season::*;
+pub use update_market_price::*;
+pub use manage_markets::*;
+pub use set_mandate::*;
+pub use trip_breaker::*;
+pub use collect_management_fee::*;
+pub use claim_fees::*;
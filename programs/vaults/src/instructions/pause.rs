@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{
+    FundState, ProtocolConfig,
+    PROTOCOL_CONFIG_SEED, FUND_SEED, VAULT_AUTHORITY_SEED, PAUSE_ALL, PAUSE_REDEMPTIONS,
+};
+use crate::errors::FundError;
+
+/// Set the protocol-wide pause bitmask
+/// Admin only - applies to every fund. Instructions that wind a fund down
+/// (end_trading, trip_breaker, finalize_close, close_position), only pay out
+/// fees already earned (claim_fees, claim_protocol_fees,
+/// collect_management_fee) or move no funds (set_mandate, manage_markets,
+/// update_market_price, close_withdrawal_request) are never paused.
+/// Cancelling a request (cancel_deposit, cancel_withdrawal) only honours the
+/// protocol and admin masks.
+/// PAUSE_REDEMPTIONS covers both redeem and redeem_outcome: cashing out
+/// resolved outcome tokens is part of paying investors, not trading.
+#[derive(Accounts)]
+pub struct SetProtocolPause<'info> {
+    #[account(
+        constraint = admin.key() == protocol_config.admin @ FundError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler_set_protocol_pause(ctx: Context<SetProtocolPause>, paused: u8) -> Result<()> {
+    require!(paused & !PAUSE_ALL == 0, FundError::InvalidPauseFlags);
+
+    let config = &mut ctx.accounts.protocol_config;
+    msg!("Protocol pause: {:#07b} -> {:#07b}", config.paused, paused);
+    config.paused = paused;
+
+    Ok(())
+}
+
+/// Set a fund's pause bitmask
+/// Manager or admin - each writes its own mask. Only the admin may pause
+/// redemptions, so a manager pause can never trap investors in a Closed fund,
+/// and cancellations ignore the manager mask so escrow can always be reclaimed.
+#[derive(Accounts)]
+pub struct SetFundPause<'info> {
+    #[account(
+        constraint = authority.key() == fund_state.manager
+            || authority.key() == protocol_config.admin @ FundError::UnauthorizedManager
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler_set_fund_pause(ctx: Context<SetFundPause>, paused: u8) -> Result<()> {
+    require!(paused & !PAUSE_ALL == 0, FundError::InvalidPauseFlags);
+
+    let is_admin = ctx.accounts.authority.key() == ctx.accounts.protocol_config.admin;
+
+    // Accrue the management fee on every state change
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    let fund = &mut ctx.accounts.fund_state;
+
+    if is_admin {
+        msg!("Fund admin pause: {:#07b} -> {:#07b}", fund.admin_paused, paused);
+        fund.admin_paused = paused;
+    } else {
+        require!(paused & PAUSE_REDEMPTIONS == 0, FundError::UnauthorizedAdmin);
+        msg!("Fund manager pause: {:#07b} -> {:#07b}", fund.paused, paused);
+        fund.paused = paused;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo, Transfer, mint_to, transfer};
+
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{
+    close_account, load_account, DepositRequest, EpochState, FeeLedger, FundState, FundStage,
+    FUND_SEED, FEE_LEDGER_SEED, VAULT_AUTHORITY_SEED, DEPOSIT_REQUEST_SEED, DEPOSIT_ESCROW_SEED, EPOCH_SEED,
+    ProtocolConfig, PROTOCOL_CONFIG_SEED, require_not_paused, PAUSE_DEPOSITS,
+};
+use crate::errors::FundError;
+
+/// Mint shares for struck deposit requests
+/// Anyone can crank this instruction
+///
+/// Requests are priced at the latest struck epoch's NAV per share after the
+/// deposit fee. A request its own strike did not process is priced at the
+/// latest strike, so holding a request back never buys a stale price. The net
+/// USDC moves from the deposit escrow into the vault, the fee to the fee
+/// escrow, and the request is closed back to the investor.
+///
+/// `remaining_accounts` = (DepositRequest, investor share account, investor
+/// wallet) triplets.
+#[derive(Accounts)]
+pub struct ProcessDeposits<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump,
+        constraint = fund_state.stage == FundStage::Trading || fund_state.stage == FundStage::Settlement @ FundError::InvalidStage
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Latest struck epoch of the current season (prices the requests)
+    #[account(
+        seeds = [EPOCH_SEED, fund_state.key().as_ref(), &epoch_state.epoch.to_le_bytes()],
+        bump = epoch_state.bump,
+        constraint = epoch_state.epoch + 1 == fund_state.current_epoch @ FundError::EpochClosed,
+        constraint = epoch_state.epoch >= fund_state.season_start_epoch @ FundError::EpochClosed
+    )]
+    pub epoch_state: Box<Account<'info, EpochState>>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Deposit escrow
+    #[account(
+        mut,
+        seeds = [DEPOSIT_ESCROW_SEED, fund_state.key().as_ref()],
+        bump
+    )]
+    pub deposit_escrow: Account<'info, TokenAccount>,
+
+    /// Vault's USDC token account
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == fund_state.vault_usdc_ata @ FundError::InvalidUsdcMint
+    )]
+    pub vault_usdc_ata: Account<'info, TokenAccount>,
+
+    /// Fee escrow (vault authority owned, holds fees until claimed)
+    #[account(
+        mut,
+        constraint = fee_escrow.key() == fund_state.fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub fee_escrow: Account<'info, TokenAccount>,
+
+    /// Fee ledger tracking claimable manager and protocol fees
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, fund_state.key().as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    /// Share mint
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ProcessDeposits<'info>>) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_DEPOSITS)?;
+
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    let batch = ctx.remaining_accounts;
+    require!(
+        !batch.is_empty() && batch.chunks_exact(3).remainder().is_empty(),
+        FundError::InvalidDepositBatch
+    );
+
+    let fund_key = ctx.accounts.fund_state.key();
+    let epoch = &ctx.accounts.epoch_state;
+    let vault_seeds = &[
+        VAULT_AUTHORITY_SEED,
+        fund_key.as_ref(),
+        &[ctx.accounts.fund_state.vault_authority_bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    let mut gross_total: u64 = 0;
+    let mut fee_total: u64 = 0;
+    let mut shares_total: u64 = 0;
+
+    for triplet in batch.chunks(3) {
+        let request_info = &triplet[0];
+        let investor_shares_info = &triplet[1];
+        let investor_info = &triplet[2];
+
+        let request = load_deposit_request(request_info, &fund_key, ctx.program_id)?;
+        require!(request.epoch <= epoch.epoch, FundError::InvalidDepositBatch);
+        require_keys_eq!(investor_info.key(), request.investor, FundError::InvalidDepositBatch);
+        require!(investor_info.is_writable, FundError::InvalidDepositBatch);
+
+        // Investor share account must be a share account owned by the requester
+        require_keys_eq!(*investor_shares_info.owner, token::ID, FundError::InvalidDepositBatch);
+        let investor_shares = {
+            let data = investor_shares_info.try_borrow_data()?;
+            TokenAccount::try_deserialize(&mut &data[..])?
+        };
+        require_keys_eq!(investor_shares.mint, ctx.accounts.fund_state.share_mint, FundError::InvalidShareMint);
+        require_keys_eq!(investor_shares.owner, request.investor, FundError::InvalidDepositBatch);
+
+        // Price at the struck NAV per share, after the deposit fee
+        let deposit_fee = ctx.accounts.fund_state.calculate_deposit_fee(request.usdc_amount);
+        let net_amount = request.usdc_amount.checked_sub(deposit_fee)
+            .ok_or(FundError::MathUnderflow)?;
+        let shares_to_mint = epoch.shares_for_subscription(net_amount)?;
+
+        if shares_to_mint > 0 {
+            let mint_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    to: investor_shares_info.clone(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                signer_seeds,
+            );
+            mint_to(mint_ctx, shares_to_mint)?;
+        }
+
+        msg!("Subscription: {} USDC (fee: {}) -> {} shares", request.usdc_amount, deposit_fee, shares_to_mint);
+
+        gross_total = gross_total.checked_add(request.usdc_amount)
+            .ok_or(FundError::MathOverflow)?;
+        fee_total = fee_total.checked_add(deposit_fee)
+            .ok_or(FundError::MathOverflow)?;
+        shares_total = shares_total.checked_add(shares_to_mint)
+            .ok_or(FundError::MathOverflow)?;
+
+        // Request is settled - return its rent to the investor
+        close_account(request_info, investor_info)?;
+    }
+
+    let net_total = gross_total - fee_total;
+
+    // Move the escrowed USDC into the vault and the fees to the fee escrow
+    let transfer_vault_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.deposit_escrow.to_account_info(),
+            to: ctx.accounts.vault_usdc_ata.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer(transfer_vault_ctx, net_total)?;
+
+    if fee_total > 0 {
+        let transfer_fee_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.deposit_escrow.to_account_info(),
+                to: ctx.accounts.fee_escrow.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_fee_ctx, fee_total)?;
+    }
+    let protocol_fee = ctx.accounts.protocol_config.protocol_share(fee_total);
+    let manager_fee = fee_total - protocol_fee;
+    ctx.accounts.fee_ledger.credit_deposit_fee(fee_total, protocol_fee)?;
+
+    // Update fund state - new capital is not profit for the performance fee
+    let fund = &mut ctx.accounts.fund_state;
+    fund.restart_hwm_if_empty(epoch.nav_per_share);
+    fund.total_shares = fund.total_shares.checked_add(shares_total)
+        .ok_or(FundError::MathOverflow)?;
+    fund.total_deposited = fund.total_deposited.checked_add(gross_total)
+        .ok_or(FundError::MathOverflow)?;
+    fund.initial_aum_usdc = fund.initial_aum_usdc.checked_add(net_total)
+        .ok_or(FundError::MathOverflow)?;
+    fund.pending_deposit_usdc = fund.pending_deposit_usdc.saturating_sub(gross_total);
+
+    msg!("Subscriptions processed at epoch {} strike", epoch.epoch);
+    msg!("USDC: {} (fees: {}, net: {})", gross_total, fee_total, net_total);
+    msg!("Fee split: manager {}, protocol {}", manager_fee, protocol_fee);
+    msg!("Shares minted: {}", shares_total);
+    msg!("Total shares: {}", fund.total_shares);
+
+    Ok(())
+}
+
+/// Load a DepositRequest from remaining_accounts and verify its PDA
+fn load_deposit_request(
+    info: &AccountInfo,
+    fund_key: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<DepositRequest> {
+    require!(info.is_writable, FundError::InvalidDepositBatch);
+
+    let request: DepositRequest = load_account(info)?;
+    require_keys_eq!(request.fund, *fund_key, FundError::InvalidDepositBatch);
+
+    let expected = Pubkey::create_program_address(
+        &[
+            DEPOSIT_REQUEST_SEED,
+            fund_key.as_ref(),
+            request.investor.as_ref(),
+            &request.epoch.to_le_bytes(),
+            &[request.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| FundError::InvalidDepositBatch)?;
+    require_keys_eq!(expected, info.key(), FundError::InvalidDepositBatch);
+
+    Ok(request)
+}
@@ -1,17 +1,36 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, Burn, Transfer, burn, transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Burn, Transfer, burn, transfer};
 
+use crate::events::EpochStruck;
+use crate::fees::MgmtFeeAccounts;
+use crate::nav::{fund_nav, split_nav_accounts};
 use crate::state::{
-    FundState, FundStage, WithdrawalRequest, RequestStatus,
-    FUND_SEED, VAULT_AUTHORITY_SEED, WITHDRAWAL_REQUEST_SEED,
+    load_account, pro_rata_fill, pro_rata_ratio, EpochState, FundState, FundStage,
+    WithdrawalPricing, WithdrawalQueue, WithdrawalRequest, RequestStatus,
+    FUND_SEED, VAULT_AUTHORITY_SEED, WITHDRAWAL_QUEUE_SEED, WITHDRAWAL_REQUEST_SEED, EPOCH_SEED,
+    RATIO_SCALE,
+    ProtocolConfig, PROTOCOL_CONFIG_SEED, require_not_paused, PAUSE_EPOCH,
 };
 use crate::errors::FundError;
 
-/// Process pending withdrawal requests at epoch boundary
+/// Strike the current epoch and fill the first batch of withdrawal requests
 /// Anyone can crank this instruction
+///
+/// Striking crystallizes the performance fee above the high-water mark, then
+/// snapshots NAV, NAV per share, the pending withdrawal shares and the
+/// USDC available for redemptions (respecting the liquidity buffer and the
+/// per-epoch redemption gate) into an EpochState PDA. Under forward pricing,
+/// fills are priced at the struck NAV per share; under locked pricing the
+/// redemption demand is valued at each request's locked NAV per share.
+/// Every pending request is then filled pro rata to its remaining shares with
+/// the same ratio, whichever transaction processes it.
+///
+/// `remaining_accounts` = NAV pairs (Position, MarketPrice) for every open
+/// position, followed by (WithdrawalRequest, investor USDC account) pairs in
+/// consecutive queue order from the head.
 #[derive(Accounts)]
 pub struct ProcessEpoch<'info> {
-    /// Cranker (anyone can call)
+    /// Cranker (anyone can call, pays for the EpochState)
     #[account(mut)]
     pub payer: Signer<'info>,
 
@@ -23,6 +42,30 @@ pub struct ProcessEpoch<'info> {
     )]
     pub fund_state: Box<Account<'info, FundState>>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Snapshot of the epoch being struck
+    #[account(
+        init,
+        payer = payer,
+        space = EpochState::LEN,
+        seeds = [EPOCH_SEED, fund_state.key().as_ref(), &fund_state.current_epoch.to_le_bytes()],
+        bump
+    )]
+    pub epoch_state: Box<Account<'info, EpochState>>,
+
+    /// FIFO withdrawal queue
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_QUEUE_SEED, fund_state.key().as_ref()],
+        bump = withdrawal_queue.bump
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+
     /// CHECK: Vault authority PDA
     #[account(
         seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
@@ -37,6 +80,14 @@ pub struct ProcessEpoch<'info> {
     )]
     pub vault_usdc_ata: Account<'info, TokenAccount>,
 
+    /// Share escrow holding the requested shares (vault authority's share ATA)
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = vault_authority,
+    )]
+    pub share_escrow: Account<'info, TokenAccount>,
+
     /// Share mint for burning
     #[account(
         mut,
@@ -44,21 +95,24 @@ pub struct ProcessEpoch<'info> {
     )]
     pub share_mint: Account<'info, Mint>,
 
-    // Note: In production, this would use remaining_accounts to pass:
-    // - Multiple WithdrawalRequest accounts
-    // - Corresponding investor share accounts
-    // - Corresponding investor USDC accounts
-    // For MVP, we process one request at a time
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-/// Process a single withdrawal request (for MVP - production would batch)
+/// Fill a further batch of withdrawal requests against the latest struck epoch
+/// Anyone can crank this instruction
+///
+/// `remaining_accounts` = (WithdrawalRequest, investor USDC account) pairs in
+/// consecutive queue order, continuing where the previous batch stopped.
 #[derive(Accounts)]
-#[instruction(request_index: u32)]
-pub struct ProcessSingleWithdrawal<'info> {
-    #[account(mut)]
+pub struct ProcessWithdrawalBatch<'info> {
     pub payer: Signer<'info>,
 
     #[account(
@@ -69,50 +123,50 @@ pub struct ProcessSingleWithdrawal<'info> {
     )]
     pub fund_state: Box<Account<'info, FundState>>,
 
-    /// CHECK: Vault authority PDA
     #[account(
-        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
-        bump = fund_state.vault_authority_bump
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
     )]
-    pub vault_authority: UncheckedAccount<'info>,
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// Vault's USDC token account
+    /// Latest struck epoch
     #[account(
         mut,
-        constraint = vault_usdc_ata.key() == fund_state.vault_usdc_ata @ FundError::InvalidUsdcMint
+        seeds = [EPOCH_SEED, fund_state.key().as_ref(), &epoch_state.epoch.to_le_bytes()],
+        bump = epoch_state.bump,
+        constraint = epoch_state.epoch + 1 == fund_state.current_epoch @ FundError::EpochClosed
     )]
-    pub vault_usdc_ata: Account<'info, TokenAccount>,
+    pub epoch_state: Box<Account<'info, EpochState>>,
 
-    /// The withdrawal request to process
+    /// FIFO withdrawal queue
     #[account(
         mut,
-        seeds = [
-            WITHDRAWAL_REQUEST_SEED,
-            fund_state.key().as_ref(),
-            withdrawal_request.investor.as_ref(),
-            &request_index.to_le_bytes()
-        ],
-        bump = withdrawal_request.bump,
-        constraint = withdrawal_request.status == RequestStatus::Pending || 
-                     withdrawal_request.status == RequestStatus::PartiallyFilled @ FundError::WithdrawalRequestInactive
+        seeds = [WITHDRAWAL_QUEUE_SEED, fund_state.key().as_ref()],
+        bump = withdrawal_queue.bump
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
     )]
-    pub withdrawal_request: Box<Account<'info, WithdrawalRequest>>,
+    pub vault_authority: UncheckedAccount<'info>,
 
-    /// Investor's share token account
+    /// Vault's USDC token account
     #[account(
         mut,
-        constraint = investor_shares.mint == fund_state.share_mint @ FundError::InvalidShareMint,
-        constraint = investor_shares.owner == withdrawal_request.investor
+        constraint = vault_usdc_ata.key() == fund_state.vault_usdc_ata @ FundError::InvalidUsdcMint
     )]
-    pub investor_shares: Account<'info, TokenAccount>,
+    pub vault_usdc_ata: Account<'info, TokenAccount>,
 
-    /// Investor's USDC token account
+    /// Share escrow holding the requested shares (vault authority's share ATA)
     #[account(
         mut,
-        constraint = investor_usdc.mint == fund_state.usdc_mint @ FundError::InvalidUsdcMint,
-        constraint = investor_usdc.owner == withdrawal_request.investor
+        associated_token::mint = share_mint,
+        associated_token::authority = vault_authority,
     )]
-    pub investor_usdc: Account<'info, TokenAccount>,
+    pub share_escrow: Account<'info, TokenAccount>,
 
     /// Share mint for burning
     #[account(
@@ -121,53 +175,270 @@ pub struct ProcessSingleWithdrawal<'info> {
     )]
     pub share_mint: Account<'info, Mint>,
 
-    /// CHECK: Investor account for signing burn (delegated authority)
-    pub investor: UncheckedAccount<'info>,
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler_process_single(
-    ctx: Context<ProcessSingleWithdrawal>,
-    request_index: u32,
-) -> Result<()> {
-    let fund = &ctx.accounts.fund_state;
-    let request = &ctx.accounts.withdrawal_request;
-    let vault_usdc = ctx.accounts.vault_usdc_ata.amount;
-    let clock = Clock::get()?;
+/// Accounts used to pay out a withdrawal fill and burn its escrowed shares
+struct FillAccounts<'a, 'info> {
+    token_program: AccountInfo<'info>,
+    vault_usdc_ata: AccountInfo<'info>,
+    share_escrow: AccountInfo<'info>,
+    share_mint: AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    signer_seeds: &'a [&'a [&'a [u8]]],
+}
 
-    // Check epoch is ready
-    let epoch_ready = clock.unix_timestamp >= fund.last_epoch_ts + fund.epoch_interval_secs;
-    // For MVP, allow anytime processing during Settlement
-    let is_settlement = fund.stage == FundStage::Settlement;
-    
-    if !epoch_ready && !is_settlement {
-        return Err(FundError::EpochNotReady.into());
+impl<'a, 'info> FillAccounts<'a, 'info> {
+    /// Fill `request` pro rata for the struck epoch
+    /// Pays USDC to the investor, burns the filled shares from escrow and
+    /// updates the request. Returns (shares filled, USDC paid).
+    fn fill(
+        &self,
+        request: &mut WithdrawalRequest,
+        investor_usdc: AccountInfo<'info>,
+        epoch: &EpochState,
+        pricing: WithdrawalPricing,
+    ) -> Result<(u64, u64)> {
+        // Only requests submitted up to this epoch, once per epoch
+        if request.epoch > epoch.epoch || request.last_filled_epoch >= epoch.epoch {
+            return Ok((0, 0));
+        }
+        request.last_filled_epoch = epoch.epoch;
+
+        // Pro-rata share of the remaining shares at the fund's withdrawal
+        // pricing mode, capped by the epoch budget
+        let usdc_per_share = request.fill_price(pricing, epoch);
+        let (shares_to_process, payout) = pro_rata_fill(
+            request.shares_remaining(),
+            epoch.fill_ratio,
+            usdc_per_share,
+            epoch.usdc_remaining(),
+        );
+
+        if shares_to_process == 0 {
+            return Ok((0, 0));
+        }
+
+        // Transfer USDC to investor (a dust remainder completes for nothing)
+        if payout > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                self.token_program.clone(),
+                Transfer {
+                    from: self.vault_usdc_ata.clone(),
+                    to: investor_usdc,
+                    authority: self.vault_authority.clone(),
+                },
+                self.signer_seeds,
+            );
+            transfer(transfer_ctx, payout)?;
+        }
+
+        // Burn the filled shares from escrow
+        let burn_ctx = CpiContext::new_with_signer(
+            self.token_program.clone(),
+            Burn {
+                mint: self.share_mint.clone(),
+                from: self.share_escrow.clone(),
+                authority: self.vault_authority.clone(),
+            },
+            self.signer_seeds,
+        );
+        burn(burn_ctx, shares_to_process)?;
+
+        // Update withdrawal request
+        request.shares_filled = request.shares_filled.checked_add(shares_to_process)
+            .ok_or(FundError::MathOverflow)?;
+        request.usdc_received = request.usdc_received.checked_add(payout)
+            .ok_or(FundError::MathOverflow)?;
+
+        if request.shares_filled >= request.shares_requested {
+            request.status = RequestStatus::Completed;
+        } else {
+            request.status = RequestStatus::PartiallyFilled;
+        }
+
+        Ok((shares_to_process, payout))
     }
 
-    let shares_remaining = request.shares_remaining();
-    require!(shares_remaining > 0, FundError::WithdrawalRequestInactive);
-
-    // Calculate how much we can pay from available USDC
-    // Use the NAV per share locked at request time
-    let usdc_per_share = request.nav_per_share_at_request;
-    let max_usdc_owed = ((shares_remaining as u128) * (usdc_per_share as u128) / 1_000_000) as u64;
-    
-    // Pay out as much as possible from available USDC
-    let payout = std::cmp::min(max_usdc_owed, vault_usdc);
-    let shares_to_process = if usdc_per_share > 0 {
-        ((payout as u128) * 1_000_000 / (usdc_per_share as u128)) as u64
-    } else {
-        0
-    };
-    
-    if payout == 0 || shares_to_process == 0 {
-        msg!("No liquidity available for this request");
-        return Ok(());
+    /// Walk (WithdrawalRequest, investor USDC account) pairs and fill each
+    /// request against the epoch snapshot
+    fn fill_batch(
+        &self,
+        fund: &mut FundState,
+        fund_key: &Pubkey,
+        epoch: &mut EpochState,
+        queue: &mut WithdrawalQueue,
+        batch: &[AccountInfo<'info>],
+        program_id: &Pubkey,
+    ) -> Result<()> {
+        require!(
+            batch.chunks_exact(2).remainder().is_empty(),
+            FundError::InvalidWithdrawalBatch
+        );
+
+        let mut requests_processed: u32 = 0;
+        let mut shares_processed: u64 = 0;
+        let mut usdc_paid: u64 = 0;
+
+        let mut locked_released: u64 = 0;
+
+        for pair in batch.chunks(2) {
+            let request_info = &pair[0];
+            let investor_usdc_info = &pair[1];
+
+            // Requests must be passed in consecutive queue order - none can be skipped
+            let mut request = load_withdrawal_request(request_info, fund_key, program_id)?;
+            require!(
+                request.sequence == epoch.cursor && request.sequence < queue.tail,
+                FundError::InvalidWithdrawalBatch
+            );
+            epoch.cursor += 1;
+
+            if request.is_terminal() {
+                msg!("Skipping inactive request {}", request.sequence);
+                queue.advance_head(&request);
+                continue;
+            }
+
+            // Investor USDC account must be a USDC account owned by the requester
+            require_keys_eq!(*investor_usdc_info.owner, token::ID, FundError::InvalidWithdrawalBatch);
+            let investor_usdc = {
+                let data = investor_usdc_info.try_borrow_data()?;
+                TokenAccount::try_deserialize(&mut &data[..])?
+            };
+            require_keys_eq!(investor_usdc.mint, fund.usdc_mint, FundError::InvalidUsdcMint);
+            require_keys_eq!(investor_usdc.owner, request.investor, FundError::InvalidWithdrawalBatch);
+
+            let locked_before = request.locked_value();
+            let (shares, payout) = self.fill(&mut request, investor_usdc_info.clone(), epoch, fund.withdrawal_pricing)?;
+            locked_released = locked_released.checked_add(locked_before - request.locked_value())
+                .ok_or(FundError::MathOverflow)?;
+
+            // Persist the updated request (fill epoch is recorded even for zero fills)
+            {
+                let mut data = request_info.try_borrow_mut_data()?;
+                request.try_serialize(&mut &mut data[..])?;
+            }
+
+            epoch.shares_filled = epoch.shares_filled.checked_add(shares)
+                .ok_or(FundError::MathOverflow)?;
+            epoch.usdc_paid = epoch.usdc_paid.checked_add(payout)
+                .ok_or(FundError::MathOverflow)?;
+            shares_processed = shares_processed.checked_add(shares)
+                .ok_or(FundError::MathOverflow)?;
+            usdc_paid = usdc_paid.checked_add(payout)
+                .ok_or(FundError::MathOverflow)?;
+            requests_processed += 1;
+            queue.advance_head(&request);
+        }
+
+        fund.total_shares = fund.total_shares.saturating_sub(shares_processed);
+        fund.pending_withdrawal_shares = fund.pending_withdrawal_shares.saturating_sub(shares_processed);
+        fund.pending_withdrawal_locked_usdc = fund.pending_withdrawal_locked_usdc.saturating_sub(locked_released);
+
+        msg!("Requests processed: {}", requests_processed);
+        msg!("Shares filled: {}", shares_processed);
+        msg!("USDC paid: {}", usdc_paid);
+        msg!("Epoch budget remaining: {}", epoch.usdc_remaining());
+        msg!("Queue head: {} (tail {})", queue.head, queue.tail);
+
+        Ok(())
     }
+}
+
+/// Strike the epoch snapshot and fill the first batch
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ProcessEpoch<'info>>) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_EPOCH)?;
+
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    let clock = Clock::get()?;
+    let fund = &ctx.accounts.fund_state;
+
+    // Check epoch is ready (the interval applies in Settlement too)
+    require!(
+        clock.unix_timestamp >= fund.last_epoch_ts + fund.epoch_interval_secs,
+        FundError::EpochNotReady
+    );
+
+    // Strike NAV and the redemption budget
+    let (nav_accounts, batch) = split_nav_accounts(fund, ctx.remaining_accounts)?;
+    let vault_usdc = ctx.accounts.vault_usdc_ata.amount;
+    let nav = fund_nav(
+        fund,
+        vault_usdc,
+        nav_accounts,
+        clock.unix_timestamp,
+        ctx.accounts.protocol_config.max_price_staleness_secs,
+    )?;
+
+    // Crystallize the performance fee above the high-water mark so leaving
+    // and remaining investors bear it pro rata
+    let (perf_fee, nav) = ctx.accounts.fund_state.crystallize_perf_fee(nav)?;
+    let fund = &ctx.accounts.fund_state;
+
+    // USDC owed to the manager is not available for redemptions
+    let free_usdc = vault_usdc.saturating_sub(fund.outstanding_perf_fee());
+    let pending_shares = fund.pending_withdrawal_shares;
+    let pending_value = match fund.withdrawal_pricing {
+        WithdrawalPricing::ForwardEpochNav => fund.usdc_for_shares(pending_shares, nav),
+        WithdrawalPricing::LockedAtRequest => fund.pending_withdrawal_locked_usdc,
+    };
+    let liquid_budget = fund.redeemable_liquidity(free_usdc, nav).min(pending_value);
+    let usdc_available = liquid_budget.min(fund.epoch_redemption_cap(nav));
+    let gated_usdc = liquid_budget - usdc_available;
+    let fill_ratio = pro_rata_ratio(usdc_available, pending_value);
+
+    let fund_key = fund.key();
+    let epoch = &mut ctx.accounts.epoch_state;
+    epoch.fund = fund_key;
+    epoch.epoch = fund.current_epoch;
+    epoch.struck_at = clock.unix_timestamp;
+    epoch.nav = nav;
+    epoch.nav_per_share = fund.nav_per_share(nav);
+    epoch.pending_shares = pending_shares;
+    epoch.usdc_available = usdc_available;
+    epoch.gated_usdc = gated_usdc;
+    epoch.fill_ratio = fill_ratio;
+    epoch.shares_filled = 0;
+    epoch.usdc_paid = 0;
+    epoch.bump = ctx.bumps.epoch_state;
+    epoch.cursor = ctx.accounts.withdrawal_queue.head;
+
+    msg!("Epoch {} struck at {}", epoch.epoch, clock.unix_timestamp);
+    msg!("NAV: {} USDC", nav);
+    msg!("NAV per share: {}", epoch.nav_per_share);
+    msg!("Performance fee crystallized: {} (high-water mark {})", perf_fee, fund.high_water_mark);
+    msg!("Pending shares: {}", pending_shares);
+    msg!("USDC available: {}", usdc_available);
+    msg!("USDC gated to next epoch: {}", gated_usdc);
+    msg!("Fill ratio: {} / {}", fill_ratio, RATIO_SCALE);
+
+    emit!(EpochStruck {
+        fund: fund_key,
+        epoch: epoch.epoch,
+        nav,
+        nav_per_share: epoch.nav_per_share,
+        pending_shares,
+        usdc_available,
+        gated_usdc,
+        fill_ratio,
+    });
 
-    // Transfer USDC to investor
-    let fund_key = ctx.accounts.fund_state.key();
     let vault_seeds = &[
         VAULT_AUTHORITY_SEED,
         fund_key.as_ref(),
@@ -175,60 +446,91 @@ pub fn handler_process_single(
     ];
     let signer_seeds = &[&vault_seeds[..]];
 
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.vault_usdc_ata.to_account_info(),
-            to: ctx.accounts.investor_usdc.to_account_info(),
-            authority: ctx.accounts.vault_authority.to_account_info(),
-        },
+    let fill_accounts = FillAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        vault_usdc_ata: ctx.accounts.vault_usdc_ata.to_account_info(),
+        share_escrow: ctx.accounts.share_escrow.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
         signer_seeds,
-    );
-    transfer(transfer_ctx, payout)?;
-
-    // Update withdrawal request
-    let request = &mut ctx.accounts.withdrawal_request;
-    request.shares_filled = request.shares_filled.checked_add(shares_to_process)
-        .ok_or(FundError::MathOverflow)?;
-    request.usdc_received = request.usdc_received.checked_add(payout)
-        .ok_or(FundError::MathOverflow)?;
-    
-    if request.shares_filled >= request.shares_requested {
-        request.status = RequestStatus::Completed;
-    } else {
-        request.status = RequestStatus::PartiallyFilled;
-    }
+    };
 
-    // Update fund state
+    // Requests submitted from now on belong to the next epoch
     let fund = &mut ctx.accounts.fund_state;
-    fund.total_shares = fund.total_shares.saturating_sub(shares_to_process);
-    fund.pending_withdrawal_shares = fund.pending_withdrawal_shares.saturating_sub(shares_to_process);
+    fund.current_epoch = fund.current_epoch.checked_add(1)
+        .ok_or(FundError::MathOverflow)?;
     fund.last_epoch_ts = clock.unix_timestamp;
 
-    msg!("Withdrawal processed");
-    msg!("Shares processed: {}", shares_to_process);
-    msg!("USDC paid: {}", payout);
-    msg!("Status: {:?}", request.status);
+    let queue = &mut ctx.accounts.withdrawal_queue;
+    fill_accounts.fill_batch(fund, &fund_key, epoch, queue, batch, ctx.program_id)?;
 
     Ok(())
 }
 
-/// Simple epoch trigger (updates last_epoch_ts)
-pub fn handler(ctx: Context<ProcessEpoch>) -> Result<()> {
-    let clock = Clock::get()?;
-    let fund = &mut ctx.accounts.fund_state;
+/// Fill a further batch against the latest struck epoch
+pub fn handler_process_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, ProcessWithdrawalBatch<'info>>,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_EPOCH)?;
+
+    // Accrue the management fee on every state change
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
 
-    // Check epoch is ready
-    require!(
-        clock.unix_timestamp >= fund.last_epoch_ts + fund.epoch_interval_secs,
-        FundError::EpochNotReady
-    );
+    let fund_key = ctx.accounts.fund_state.key();
+    let vault_seeds = &[
+        VAULT_AUTHORITY_SEED,
+        fund_key.as_ref(),
+        &[ctx.accounts.fund_state.vault_authority_bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
 
-    // Update epoch timestamp
-    fund.last_epoch_ts = clock.unix_timestamp;
+    let fill_accounts = FillAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        vault_usdc_ata: ctx.accounts.vault_usdc_ata.to_account_info(),
+        share_escrow: ctx.accounts.share_escrow.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
 
-    msg!("Epoch triggered at {}", clock.unix_timestamp);
-    msg!("Pending shares: {}", fund.pending_withdrawal_shares);
+    let fund = &mut ctx.accounts.fund_state;
+    let epoch = &mut ctx.accounts.epoch_state;
+    msg!("Processing batch for epoch {}", epoch.epoch);
+
+    let queue = &mut ctx.accounts.withdrawal_queue;
+    fill_accounts.fill_batch(fund, &fund_key, epoch, queue, ctx.remaining_accounts, ctx.program_id)?;
 
     Ok(())
 }
+
+/// Load a WithdrawalRequest from remaining_accounts and verify its PDA
+fn load_withdrawal_request(
+    info: &AccountInfo,
+    fund_key: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<WithdrawalRequest> {
+    require!(info.is_writable, FundError::InvalidWithdrawalBatch);
+
+    let request: WithdrawalRequest = load_account(info)?;
+    require_keys_eq!(request.fund, *fund_key, FundError::InvalidWithdrawalBatch);
+
+    let expected = Pubkey::create_program_address(
+        &[
+            WITHDRAWAL_REQUEST_SEED,
+            fund_key.as_ref(),
+            &request.sequence.to_le_bytes(),
+            &[request.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| FundError::InvalidWithdrawalBatch)?;
+    require_keys_eq!(expected, info.key(), FundError::InvalidWithdrawalBatch);
+
+    Ok(request)
+}
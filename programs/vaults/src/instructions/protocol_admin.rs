@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    ProtocolConfig, PROTOCOL_CONFIG_SEED,
+    HARD_MAX_DEPOSIT_FEE_BPS, HARD_MAX_PERF_FEE_BPS, HARD_MAX_MGMT_FEE_BPS, HARD_MAX_PROTOCOL_FEE_BPS,
+};
+use crate::errors::FundError;
+
+/// Update protocol configuration
+/// Admin only - every change is bounded and logged. Fee caps only apply to
+/// funds created afterwards; existing funds keep their own fees. The USDC mint
+/// is fixed at initialize_protocol since fund vaults and escrows are bound to it.
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        constraint = admin.key() == protocol_config.admin @ FundError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+/// Protocol config changes - `None` leaves a field unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ProtocolConfigUpdate {
+    pub max_deposit_fee_bps: Option<u16>,
+    pub max_perf_fee_bps: Option<u16>,
+    pub max_mgmt_fee_bps: Option<u16>,
+    pub max_early_exit_fee_bps: Option<u16>,
+    pub min_buffer_bps: Option<u16>,
+    pub default_epoch_interval_secs: Option<i64>,
+    pub max_price_staleness_secs: Option<i64>,
+    pub allowed_dflow_program: Option<Pubkey>,
+    pub protocol_fee_recipient: Option<Pubkey>,
+    pub protocol_fee_bps: Option<u16>,
+    pub oracle_authority: Option<Pubkey>,
+}
+
+pub fn handler_update_config(
+    ctx: Context<UpdateProtocolConfig>,
+    update: ProtocolConfigUpdate,
+) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+
+    if let Some(bps) = update.max_deposit_fee_bps {
+        require!(bps <= HARD_MAX_DEPOSIT_FEE_BPS, FundError::InvalidProtocolConfig);
+        msg!("max_deposit_fee_bps: {} -> {}", config.max_deposit_fee_bps, bps);
+        config.max_deposit_fee_bps = bps;
+    }
+    if let Some(bps) = update.max_perf_fee_bps {
+        require!(bps <= HARD_MAX_PERF_FEE_BPS, FundError::InvalidProtocolConfig);
+        msg!("max_perf_fee_bps: {} -> {}", config.max_perf_fee_bps, bps);
+        config.max_perf_fee_bps = bps;
+    }
+    if let Some(bps) = update.max_mgmt_fee_bps {
+        require!(bps <= HARD_MAX_MGMT_FEE_BPS, FundError::InvalidProtocolConfig);
+        msg!("max_mgmt_fee_bps: {} -> {}", config.max_mgmt_fee_bps, bps);
+        config.max_mgmt_fee_bps = bps;
+    }
+    if let Some(bps) = update.max_early_exit_fee_bps {
+        msg!("max_early_exit_fee_bps: {} -> {}", config.max_early_exit_fee_bps, bps);
+        config.max_early_exit_fee_bps = bps;
+    }
+    if let Some(bps) = update.min_buffer_bps {
+        msg!("min_buffer_bps: {} -> {}", config.min_buffer_bps, bps);
+        config.min_buffer_bps = bps;
+    }
+    if let Some(secs) = update.default_epoch_interval_secs {
+        msg!("default_epoch_interval_secs: {} -> {}", config.default_epoch_interval_secs, secs);
+        config.default_epoch_interval_secs = secs;
+    }
+    if let Some(secs) = update.max_price_staleness_secs {
+        msg!("max_price_staleness_secs: {} -> {}", config.max_price_staleness_secs, secs);
+        config.max_price_staleness_secs = secs;
+    }
+    config.validate_risk_bounds()?;
+
+    if let Some(program) = update.allowed_dflow_program {
+        require!(program != Pubkey::default(), FundError::InvalidProtocolConfig);
+        msg!("allowed_dflow_program: {} -> {}", config.allowed_dflow_program, program);
+        config.allowed_dflow_program = program;
+    }
+    if let Some(recipient) = update.protocol_fee_recipient {
+        require!(recipient != Pubkey::default(), FundError::InvalidProtocolConfig);
+        msg!("protocol_fee_recipient: {} -> {}", config.protocol_fee_recipient, recipient);
+        config.protocol_fee_recipient = recipient;
+    }
+    if let Some(bps) = update.protocol_fee_bps {
+        require!(bps <= HARD_MAX_PROTOCOL_FEE_BPS, FundError::InvalidProtocolConfig);
+        msg!("protocol_fee_bps: {} -> {}", config.protocol_fee_bps, bps);
+        config.protocol_fee_bps = bps;
+    }
+    if let Some(oracle) = update.oracle_authority {
+        require!(oracle != Pubkey::default(), FundError::InvalidProtocolConfig);
+        msg!("oracle_authority: {} -> {}", config.oracle_authority, oracle);
+        config.oracle_authority = oracle;
+    }
+
+    msg!("Protocol config updated by {}", ctx.accounts.admin.key());
+
+    Ok(())
+}
+
+/// Propose a new protocol admin (step 1 of 2)
+/// Admin only - the new key must call accept_admin. Proposing the default
+/// pubkey clears a pending proposal.
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        constraint = admin.key() == protocol_config.admin @ FundError::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler_propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+    config.pending_admin = new_admin;
+
+    msg!("Admin proposed: {} -> {}", config.admin, new_admin);
+
+    Ok(())
+}
+
+/// Accept the protocol admin role (step 2 of 2)
+/// Must be signed by the proposed admin
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        constraint = new_admin.key() == protocol_config.pending_admin @ FundError::UnauthorizedPendingAdmin
+    )]
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+pub fn handler_accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+    let previous_admin = config.admin;
+
+    config.admin = ctx.accounts.new_admin.key();
+    config.pending_admin = Pubkey::default();
+
+    msg!("Admin transferred: {} -> {}", previous_admin, config.admin);
+
+    Ok(())
+}
@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Burn, Transfer};
 
-use crate::state::{FundState, FundStage, FUND_SEED, VAULT_AUTHORITY_SEED, SHARE_MINT_SEED};
+use crate::fees::{MgmtFeeAccounts, PerfFeeAccounts};
+use crate::nav::fund_nav;
+use crate::state::{
+    require_not_paused, FeeLedger, FundState, FundStage, ProtocolConfig,
+    FUND_SEED, FEE_LEDGER_SEED, VAULT_AUTHORITY_SEED, SHARE_MINT_SEED, PROTOCOL_CONFIG_SEED, PAUSE_REDEMPTIONS,
+};
 use crate::errors::FundError;
 
 #[derive(Accounts)]
@@ -17,6 +22,12 @@ pub struct Redeem<'info> {
     )]
     pub fund_state: Account<'info, FundState>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// CHECK: PDA for vault authority
     #[account(
         seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
@@ -46,12 +57,20 @@ pub struct Redeem<'info> {
     )]
     pub vault_usdc_ata: Account<'info, TokenAccount>,
 
-    /// Manager's fee receiving USDC account
+    /// Fee escrow (vault authority owned, holds fees until claimed)
+    #[account(
+        mut,
+        constraint = fee_escrow.key() == fund_state.fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub fee_escrow: Account<'info, TokenAccount>,
+
+    /// Fee ledger tracking claimable manager and protocol fees
     #[account(
         mut,
-        constraint = manager_fee_ata.key() == fund_state.manager_fee_ata
+        seeds = [FEE_LEDGER_SEED, fund_state.key().as_ref()],
+        bump = fee_ledger.bump
     )]
-    pub manager_fee_ata: Account<'info, TokenAccount>,
+    pub fee_ledger: Account<'info, FeeLedger>,
 
     /// Investor's share token account
     #[account(
@@ -60,10 +79,28 @@ pub struct Redeem<'info> {
     )]
     pub investor_share_ata: Account<'info, TokenAccount>,
 
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
 }
 
 pub fn handler(ctx: Context<Redeem>, shares: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_REDEMPTIONS)?;
+
+    // Accrue the management fee on every state change
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
     require!(shares > 0, FundError::ZeroWithdrawal);
     require!(
         ctx.accounts.investor_share_ata.amount >= shares,
@@ -83,31 +120,29 @@ pub fn handler(ctx: Context<Redeem>, shares: u64) -> Result<()> {
     let signer_seeds = &[&seeds[..]];
 
     // Pay performance fee on first redemption (if due and unpaid)
-    if fund.perf_fee_due_usdc > 0 && !fund.perf_fee_paid {
-        let perf_fee = fund.perf_fee_due_usdc;
-        
-        // Transfer performance fee to manager
-        let transfer_fee_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault_usdc_ata.to_account_info(),
-                to: ctx.accounts.manager_fee_ata.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-            },
-            signer_seeds,
-        );
-        token::transfer(transfer_fee_ctx, perf_fee)?;
-        
-        fund.perf_fee_paid = true;
-        msg!("Performance fee paid to manager: {} USDC", perf_fee);
-    }
+    let perf_fee_accounts = PerfFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        vault_usdc_ata: ctx.accounts.vault_usdc_ata.to_account_info(),
+        fee_escrow: ctx.accounts.fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+    perf_fee_accounts.pay(fund, &ctx.accounts.protocol_config, &mut ctx.accounts.fee_ledger)?;
 
     // Reload vault balance after potential fee payment
     ctx.accounts.vault_usdc_ata.reload()?;
     let vault_balance = ctx.accounts.vault_usdc_ata.amount;
+    let clock = Clock::get()?;
+    let nav = fund_nav(
+        fund,
+        vault_balance,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+        ctx.accounts.protocol_config.max_price_staleness_secs,
+    )?;
 
     // Calculate USDC to return for shares
-    let usdc_amount = fund.usdc_for_shares(shares, vault_balance);
+    let usdc_amount = fund.usdc_for_shares(shares, nav);
     
     require!(
         vault_balance >= usdc_amount,
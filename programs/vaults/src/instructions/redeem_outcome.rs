@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::dflow::{validate_and_execute_bundle, DFlowInstruction};
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{
+    AllowedMarket, FundState, FundStage, MarketStatus, Position, ProtocolConfig,
+    FUND_SEED, VAULT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED, POSITION_SEED, ALLOWED_MARKET_SEED,
+    require_not_paused, PAUSE_REDEMPTIONS,
+};
+use crate::errors::FundError;
+
+/// Redeem resolved-market outcome tokens held by the vault into USDC
+///
+/// Permissionless: anyone can crank the redemption of outcome tokens once the
+/// admin has resolved the market. The redemption is a single instruction that
+/// must target the whitelisted DFlow program and is executed with vault
+/// authority seeds; its accounts are passed through `remaining_accounts`.
+///
+/// The winning mint must pay at least par (1 USDC per token) so the
+/// instruction cannot be used to dump positions; losing tokens are worthless
+/// and may be burned for nothing.
+///
+/// Gated by PAUSE_REDEMPTIONS rather than PAUSE_TRADING: it only turns
+/// resolved positions into USDC for redeeming investors, so a trading pause
+/// must not strand a Closed fund's payouts.
+#[derive(Accounts)]
+pub struct RedeemOutcome<'info> {
+    /// Cranker (anyone can call)
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump,
+        constraint = fund_state.stage == FundStage::Trading || fund_state.stage == FundStage::Settlement @ FundError::InvalidStage
+    )]
+    pub fund_state: Account<'info, FundState>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Vault's USDC token account (credited by the redemption)
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == fund_state.vault_usdc_ata
+    )]
+    pub vault_usdc_ata: Account<'info, TokenAccount>,
+
+    /// Position being redeemed
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, fund_state.key().as_ref(), position.outcome_mint.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Allowlist entry of the position's market (must be resolved)
+    #[account(
+        mut,
+        seeds = [ALLOWED_MARKET_SEED, position.market_ledger.as_ref()],
+        bump = allowed_market.bump,
+        constraint = allowed_market.status == MarketStatus::Resolved @ FundError::MarketNotResolved
+    )]
+    pub allowed_market: Box<Account<'info, AllowedMarket>>,
+
+    /// Vault's outcome token ATA for the position mint
+    #[account(
+        mut,
+        constraint = outcome_token_ata.key() == get_associated_token_address(
+            &vault_authority.key(),
+            &position.outcome_mint
+        ) @ FundError::InvalidTokenAccountOwner
+    )]
+    pub outcome_token_ata: Account<'info, TokenAccount>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, RedeemOutcome<'info>>,
+    redemption_instructions: Vec<DFlowInstruction>,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_REDEMPTIONS)?;
+
+    // Accrue the management fee on every state change
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    require!(redemption_instructions.len() == 1, FundError::InvalidRedemptionBundle);
+
+    let fund = &ctx.accounts.fund_state;
+    let is_winner = ctx.accounts.position.outcome_mint == ctx.accounts.allowed_market.winning_mint;
+
+    let usdc_before = ctx.accounts.vault_usdc_ata.amount;
+    let outcome_before = ctx.accounts.outcome_token_ata.amount;
+
+    // Accounts available to the bundle: remaining_accounts plus the vault accounts
+    let mut account_infos = ctx.remaining_accounts.to_vec();
+    account_infos.push(ctx.accounts.vault_authority.to_account_info());
+    account_infos.push(ctx.accounts.vault_usdc_ata.to_account_info());
+    account_infos.push(ctx.accounts.outcome_token_ata.to_account_info());
+
+    let fund_key = fund.key();
+    let seeds = &[
+        VAULT_AUTHORITY_SEED,
+        fund_key.as_ref(),
+        &[fund.vault_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    validate_and_execute_bundle(
+        &redemption_instructions,
+        &ctx.accounts.protocol_config.allowed_dflow_program,
+        &ctx.accounts.vault_authority.to_account_info(),
+        &[ctx.accounts.vault_usdc_ata.key(), ctx.accounts.outcome_token_ata.key()],
+        &account_infos,
+        signer_seeds,
+    )?;
+
+    ctx.accounts.vault_usdc_ata.reload()?;
+    ctx.accounts.outcome_token_ata.reload()?;
+
+    // Outcome tokens may only be burned, USDC may only be credited
+    let tokens_redeemed = outcome_before
+        .checked_sub(ctx.accounts.outcome_token_ata.amount)
+        .ok_or(FundError::InvalidTradeInstruction)?;
+    let usdc_received = ctx.accounts.vault_usdc_ata.amount
+        .checked_sub(usdc_before)
+        .ok_or(FundError::InvalidTradeInstruction)?;
+    require!(tokens_redeemed > 0, FundError::InvalidTradeInstruction);
+
+    // Winning tokens redeem 1:1 for USDC (both use 6 decimals)
+    if is_winner {
+        require!(usdc_received >= tokens_redeemed, FundError::RedemptionBelowPar);
+    }
+
+    // Realize the position
+    let position = &mut ctx.accounts.position;
+    let was_open = position.is_open();
+    let untracked = position.sync_balance(outcome_before);
+    if untracked > 0 {
+        msg!("Untracked outcome tokens added to position: {}", untracked);
+    }
+    position.apply_sell(tokens_redeemed, usdc_received)?;
+    let is_open = position.is_open();
+
+    let fund = &mut ctx.accounts.fund_state;
+    let market = &mut ctx.accounts.allowed_market;
+    if !was_open && is_open {
+        fund.open_position_count = fund.open_position_count.checked_add(1)
+            .ok_or(FundError::MathOverflow)?;
+        market.open_positions = market.open_positions.checked_add(1)
+            .ok_or(FundError::MathOverflow)?;
+    } else if was_open && !is_open {
+        fund.open_position_count = fund.open_position_count.checked_sub(1)
+            .ok_or(FundError::MathUnderflow)?;
+        market.open_positions = market.open_positions.checked_sub(1)
+            .ok_or(FundError::MathUnderflow)?;
+    }
+
+    msg!("Outcome redeemed: {}", position.outcome_mint);
+    msg!("Tokens redeemed: {}", tokens_redeemed);
+    msg!("USDC received: {}", usdc_received);
+    msg!("Realized PnL: {}", position.realized_pnl);
+    msg!("Open positions: {}", fund.open_position_count);
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
+
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{
+    DepositRequest, FundState, FundStage,
+    FUND_SEED, VAULT_AUTHORITY_SEED, DEPOSIT_REQUEST_SEED, DEPOSIT_ESCROW_SEED,
+    ProtocolConfig, PROTOCOL_CONFIG_SEED, require_not_paused, PAUSE_DEPOSITS,
+};
+use crate::errors::FundError;
+
+/// Subscribe to a fund during Trading stage
+/// Escrows USDC in the fund's deposit escrow; shares are minted by
+/// process_deposits at the current epoch's struck NAV per share.
+/// Repeated requests in the same epoch top up the same DepositRequest.
+#[derive(Accounts)]
+pub struct RequestDeposit<'info> {
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump,
+        constraint = fund_state.stage == FundStage::Trading @ FundError::DepositsNotAllowed
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = DepositRequest::LEN,
+        seeds = [
+            DEPOSIT_REQUEST_SEED,
+            fund_state.key().as_ref(),
+            investor.key().as_ref(),
+            &fund_state.current_epoch.to_le_bytes()
+        ],
+        bump
+    )]
+    pub deposit_request: Box<Account<'info, DepositRequest>>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// USDC mint
+    #[account(
+        constraint = usdc_mint.key() == fund_state.usdc_mint @ FundError::InvalidUsdcMint
+    )]
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// Deposit escrow (kept out of the vault so it does not count toward NAV)
+    #[account(
+        init_if_needed,
+        payer = investor,
+        seeds = [DEPOSIT_ESCROW_SEED, fund_state.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = vault_authority,
+    )]
+    pub deposit_escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Investor's USDC token account
+    #[account(
+        mut,
+        constraint = investor_usdc_ata.mint == fund_state.usdc_mint @ FundError::InvalidUsdcMint
+    )]
+    pub investor_usdc_ata: Account<'info, TokenAccount>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RequestDeposit>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_DEPOSITS)?;
+
+    // Accrue the management fee on every state change
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    require!(amount > 0, FundError::ZeroDeposit);
+    let clock = Clock::get()?;
+
+    // Escrow the USDC
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.investor_usdc_ata.to_account_info(),
+            to: ctx.accounts.deposit_escrow.to_account_info(),
+            authority: ctx.accounts.investor.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, amount)?;
+
+    // Initialize or top up the request
+    let fund_key = ctx.accounts.fund_state.key();
+    let current_epoch = ctx.accounts.fund_state.current_epoch;
+    let request = &mut ctx.accounts.deposit_request;
+    if request.fund == Pubkey::default() {
+        request.fund = fund_key;
+        request.investor = ctx.accounts.investor.key();
+        request.epoch = current_epoch;
+        request.usdc_amount = 0;
+        request.bump = ctx.bumps.deposit_request;
+    }
+    request.usdc_amount = request.usdc_amount
+        .checked_add(amount)
+        .ok_or(FundError::MathOverflow)?;
+    request.requested_at = clock.unix_timestamp;
+
+    // Update fund state
+    let fund = &mut ctx.accounts.fund_state;
+    fund.pending_deposit_usdc = fund.pending_deposit_usdc
+        .checked_add(amount)
+        .ok_or(FundError::MathOverflow)?;
+
+    msg!("Deposit request: {} USDC for epoch {}", amount, current_epoch);
+    msg!("Total requested this epoch: {}", request.usdc_amount);
+    msg!("Pending deposits: {}", fund.pending_deposit_usdc);
+
+    Ok(())
+}
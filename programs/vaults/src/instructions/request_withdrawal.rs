@@ -1,14 +1,21 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
 
+use crate::fees::MgmtFeeAccounts;
+use crate::nav::fund_nav;
 use crate::state::{
-    FundState, FundStage, WithdrawalRequest, RequestStatus,
-    FUND_SEED, WITHDRAWAL_REQUEST_SEED,
+    FundState, FundStage, WithdrawalQueue, WithdrawalRequest, RequestStatus,
+    FUND_SEED, VAULT_AUTHORITY_SEED, WITHDRAWAL_QUEUE_SEED, WITHDRAWAL_REQUEST_SEED,
+    ProtocolConfig, PROTOCOL_CONFIG_SEED, require_not_paused, PAUSE_WITHDRAWALS,
 };
 use crate::errors::FundError;
 
 /// Request a withdrawal during Trading stage
-/// Creates a WithdrawalRequest PDA that will be processed at the next epoch
+/// Creates a WithdrawalRequest PDA at the queue tail that will be processed
+/// at the next epoch
+/// The requested shares are escrowed in the vault authority's share ATA
+/// until they are burned (filled) or returned (cancelled)
 #[derive(Accounts)]
 pub struct RequestWithdrawal<'info> {
     #[account(mut)]
@@ -22,6 +29,20 @@ pub struct RequestWithdrawal<'info> {
     )]
     pub fund_state: Box<Account<'info, FundState>>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// FIFO queue assigning the request sequence
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_QUEUE_SEED, fund_state.key().as_ref()],
+        bump = withdrawal_queue.bump
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+
     #[account(
         init,
         payer = investor,
@@ -29,15 +50,38 @@ pub struct RequestWithdrawal<'info> {
         seeds = [
             WITHDRAWAL_REQUEST_SEED,
             fund_state.key().as_ref(),
-            investor.key().as_ref(),
-            &fund_state.pending_request_count.to_le_bytes()
+            &withdrawal_queue.tail.to_le_bytes()
         ],
         bump
     )]
     pub withdrawal_request: Box<Account<'info, WithdrawalRequest>>,
 
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Share mint
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Share escrow (vault authority's share ATA)
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = share_mint,
+        associated_token::authority = vault_authority,
+    )]
+    pub share_escrow: Box<Account<'info, TokenAccount>>,
+
     /// Investor's share token account
     #[account(
+        mut,
         constraint = investor_shares.mint == fund_state.share_mint @ FundError::InvalidShareMint,
         constraint = investor_shares.owner == investor.key()
     )]
@@ -49,10 +93,30 @@ pub struct RequestWithdrawal<'info> {
     )]
     pub vault_usdc_ata: Account<'info, TokenAccount>,
 
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<RequestWithdrawal>, shares: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_WITHDRAWALS)?;
+
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
     let fund = &ctx.accounts.fund_state;
     let vault_usdc = ctx.accounts.vault_usdc_ata.amount;
     let clock = Clock::get()?;
@@ -63,12 +127,26 @@ pub fn handler(ctx: Context<RequestWithdrawal>, shares: u64) -> Result<()> {
         FundError::InsufficientShares
     );
 
-    // Calculate NAV per share at request time (locks in the rate)
-    let nav_per_share = if fund.total_shares > 0 {
-        ((vault_usdc as u128) * 1_000_000 / (fund.total_shares as u128)) as u64
-    } else {
-        1_000_000 // 1 USDC per share
-    };
+    // NAV per share at request time (the fill price under LockedAtRequest pricing)
+    let nav = fund_nav(
+        fund,
+        vault_usdc,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+        ctx.accounts.protocol_config.max_price_staleness_secs,
+    )?;
+    let nav_per_share = fund.nav_per_share(nav);
+
+    // Escrow the requested shares
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.investor_shares.to_account_info(),
+            to: ctx.accounts.share_escrow.to_account_info(),
+            authority: ctx.accounts.investor.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, shares)?;
 
     // Initialize withdrawal request
     let request = &mut ctx.accounts.withdrawal_request;
@@ -79,6 +157,9 @@ pub fn handler(ctx: Context<RequestWithdrawal>, shares: u64) -> Result<()> {
     request.usdc_received = 0;
     request.nav_per_share_at_request = nav_per_share;
     request.requested_at = clock.unix_timestamp;
+    request.sequence = ctx.accounts.withdrawal_queue.tail;
+    request.epoch = fund.current_epoch;
+    request.last_filled_epoch = 0;
     request.status = RequestStatus::Pending;
     request.bump = ctx.bumps.withdrawal_request;
 
@@ -87,14 +168,20 @@ pub fn handler(ctx: Context<RequestWithdrawal>, shares: u64) -> Result<()> {
     fund.pending_withdrawal_shares = fund.pending_withdrawal_shares
         .checked_add(shares)
         .ok_or(FundError::MathOverflow)?;
-    fund.pending_request_count = fund.pending_request_count
+    fund.pending_withdrawal_locked_usdc = fund.pending_withdrawal_locked_usdc
+        .checked_add(ctx.accounts.withdrawal_request.locked_value())
+        .ok_or(FundError::MathOverflow)?;
+
+    let queue = &mut ctx.accounts.withdrawal_queue;
+    queue.tail = queue.tail
         .checked_add(1)
         .ok_or(FundError::MathOverflow)?;
 
     msg!("Withdrawal request created");
-    msg!("Shares: {}", shares);
+    msg!("Shares escrowed: {}", shares);
     msg!("NAV per share: {}", nav_per_share);
-    msg!("Request ID: {}", fund.pending_request_count - 1);
+    msg!("Pricing: {:?} (epoch {})", fund.withdrawal_pricing, fund.current_epoch);
+    msg!("Request sequence: {}", queue.tail - 1);
 
     Ok(())
 }
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::fees::PerfFeeAccounts;
+use crate::state::{
+    FeeLedger, FundState, FundStage, ProtocolConfig,
+    FUND_SEED, FEE_LEDGER_SEED, VAULT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED,
+    require_not_paused, PAUSE_DEPOSITS,
+};
+use crate::errors::FundError;
+
+/// Roll a Closed fund into a new season
+/// Manager only - remaining shareholders stay in, deposits reopen and the
+/// season baseline is reset to the current NAV. The performance fee keeps
+/// accruing only above the high-water mark carried over from earlier seasons.
+/// Any performance fee still owed for the closed season is moved to the fee
+/// escrow first.
+///
+/// Every position record must have been closed (close_position) and every
+/// withdrawal request settled or cancelled, so no escrowed shares or stale
+/// positions leak into the new season. Terminal requests still in the queue
+/// are carried over and skipped by the next epoch.
+/// Blocked while deposits are paused, since the new season reopens them.
+#[derive(Accounts)]
+pub struct RollSeason<'info> {
+    pub manager: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump,
+        constraint = fund_state.manager == manager.key() @ FundError::UnauthorizedManager,
+        constraint = fund_state.stage == FundStage::Closed @ FundError::InvalidStage,
+        constraint = fund_state.position_count == 0 @ FundError::PositionsNotClosed,
+        constraint = fund_state.pending_withdrawal_shares == 0 @ FundError::WithdrawalQueueNotDrained
+    )]
+    pub fund_state: Account<'info, FundState>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Vault's USDC token account
+    #[account(
+        mut,
+        constraint = vault_usdc_ata.key() == fund_state.vault_usdc_ata
+    )]
+    pub vault_usdc_ata: Account<'info, TokenAccount>,
+
+    /// Fee escrow (vault authority owned, holds fees until claimed)
+    #[account(
+        mut,
+        constraint = fee_escrow.key() == fund_state.fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub fee_escrow: Account<'info, TokenAccount>,
+
+    /// Fee ledger tracking claimable manager and protocol fees
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, fund_state.key().as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RollSeason>, trading_start_ts: i64, trading_end_ts: i64) -> Result<()> {
+    // Rolling reopens deposits
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_DEPOSITS)?;
+
+    let clock = Clock::get()?;
+
+    // Validate the new trading period
+    require!(
+        trading_start_ts > clock.unix_timestamp,
+        FundError::TradingStartInPast
+    );
+    require!(
+        trading_end_ts > trading_start_ts,
+        FundError::InvalidTradingPeriod
+    );
+
+    let fund = &mut ctx.accounts.fund_state;
+
+    // Settle the closed season's performance fee (if due and unpaid)
+    let fund_key = fund.key();
+    let seeds = &[
+        VAULT_AUTHORITY_SEED,
+        fund_key.as_ref(),
+        &[fund.vault_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let perf_fee_accounts = PerfFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        vault_usdc_ata: ctx.accounts.vault_usdc_ata.to_account_info(),
+        fee_escrow: ctx.accounts.fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        signer_seeds,
+    };
+    perf_fee_accounts.pay(fund, &ctx.accounts.protocol_config, &mut ctx.accounts.fee_ledger)?;
+
+    // Positions are closed, so vault USDC is the full NAV
+    ctx.accounts.vault_usdc_ata.reload()?;
+    let nav = ctx.accounts.vault_usdc_ata.amount;
+
+    // Start the new season
+    fund.season = fund.season.checked_add(1)
+        .ok_or(FundError::MathOverflow)?;
+    fund.trading_start_ts = trading_start_ts;
+    fund.trading_end_ts = trading_end_ts;
+    fund.stage = FundStage::Open;
+    fund.season_start_epoch = fund.current_epoch;
+
+    // Reset the season baseline and the drawdown breaker (the high-water mark
+    // carries over unless every share has been redeemed)
+    let nav_per_share = fund.nav_per_share(nav);
+    fund.restart_hwm_if_empty(nav_per_share);
+    fund.initial_aum_usdc = nav;
+    fund.perf_fee_due_usdc = 0;
+    fund.perf_fee_paid = false;
+    fund.start_nav_per_share = 0;
+    fund.breaker_tripped = false;
+
+    // No shares are pending, so no locked demand remains
+    fund.pending_withdrawal_locked_usdc = 0;
+
+    // Management fee did not accrue while Closed
+    fund.last_mgmt_accrual_ts = clock.unix_timestamp;
+
+    msg!("Season {} opened", fund.season);
+    msg!("Baseline NAV: {} USDC ({} per share)", nav, fund.nav_per_share(nav));
+    msg!("High-water mark: {}", fund.high_water_mark);
+    msg!("Trading window: {} to {}", trading_start_ts, trading_end_ts);
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{FundMandate, FundState, FundStage, FUND_SEED, VAULT_AUTHORITY_SEED, MANDATE_SEED, MAX_MANDATE_MINTS};
+use crate::errors::FundError;
+
+/// Attach (or replace) the fund's strategy mandate
+/// Manager only, Open stage only - the mandate is immutable once trading starts
+/// Never paused: it moves no funds and can only narrow what trades are allowed
+#[derive(Accounts)]
+pub struct SetMandate<'info> {
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump,
+        constraint = fund_state.manager == manager.key() @ FundError::UnauthorizedManager,
+        constraint = fund_state.stage == FundStage::Open @ FundError::InvalidStage
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = FundMandate::LEN,
+        seeds = [MANDATE_SEED, fund_state.key().as_ref()],
+        bump
+    )]
+    pub mandate: Box<Account<'info, FundMandate>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Mandate parameters - zero / empty values mean "unconstrained"
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MandateParams {
+    /// Allowed market categories as a `MarketCategory` bitmask
+    pub allowed_categories: u8,
+    /// Explicit list of tradable outcome mints
+    pub allowed_mints: Vec<Pubkey>,
+    /// Max cost basis in a single market (YES and NO combined) in bps of NAV
+    pub max_position_bps: u16,
+    /// Max number of concurrent open positions
+    pub max_open_positions: u16,
+}
+
+pub fn handler(ctx: Context<SetMandate>, params: MandateParams) -> Result<()> {
+    // Accrue the management fee on every state change
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    require!(
+        params.allowed_mints.len() <= MAX_MANDATE_MINTS,
+        FundError::InvalidMandate
+    );
+    require!(params.max_position_bps <= 10_000, FundError::InvalidMandate);
+
+    let fund_key = ctx.accounts.fund_state.key();
+    let mandate = &mut ctx.accounts.mandate;
+
+    mandate.fund = fund_key;
+    mandate.allowed_categories = params.allowed_categories;
+    mandate.allowed_mints = params.allowed_mints;
+    mandate.max_position_bps = params.max_position_bps;
+    mandate.max_open_positions = params.max_open_positions;
+    mandate.bump = ctx.bumps.mandate;
+
+    let fund = &mut ctx.accounts.fund_state;
+    fund.has_mandate = true;
+
+    msg!("Mandate set for fund {}", fund.fund_id);
+    msg!("Allowed categories: {:#010b}", mandate.allowed_categories);
+    msg!("Allowed mints: {}", mandate.allowed_mints.len());
+    msg!("Max position: {} bps", mandate.max_position_bps);
+    msg!("Max open positions: {}", mandate.max_open_positions);
+
+    Ok(())
+}
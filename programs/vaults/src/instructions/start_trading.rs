@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::state::{FundState, FundStage, FUND_SEED, VAULT_AUTHORITY_SEED};
+use crate::fees::MgmtFeeAccounts;
+use crate::state::{
+    require_not_paused, FundState, FundStage, ProtocolConfig,
+    FUND_SEED, VAULT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED, PAUSE_TRADING,
+};
 use crate::errors::FundError;
 
 #[derive(Accounts)]
@@ -16,14 +20,54 @@ pub struct StartTrading<'info> {
     )]
     pub fund_state: Account<'info, FundState>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// Vault's USDC token account (to snapshot initial AUM)
     #[account(
         constraint = vault_usdc_ata.key() == fund_state.vault_usdc_ata
     )]
     pub vault_usdc_ata: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
 }
 
 pub fn handler(ctx: Context<StartTrading>) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_TRADING)?;
+
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
     let fund = &mut ctx.accounts.fund_state;
     let clock = Clock::get()?;
 
@@ -36,12 +80,21 @@ pub fn handler(ctx: Context<StartTrading>) -> Result<()> {
     // Snapshot initial AUM (vault USDC balance at trading start)
     let initial_aum = ctx.accounts.vault_usdc_ata.amount;
     fund.initial_aum_usdc = initial_aum;
+    // No positions exist during Open, so vault USDC is the full NAV
+    fund.start_nav_per_share = fund.nav_per_share(initial_aum);
+    // First season (or an empty fund) sets the high-water mark; later
+    // seasons keep theirs
+    if fund.high_water_mark == 0 || fund.total_shares == 0 {
+        fund.high_water_mark = fund.start_nav_per_share;
+    }
 
     // Transition to Trading stage
     fund.stage = FundStage::Trading;
 
     msg!("Trading started");
     msg!("Initial AUM: {} USDC", initial_aum);
+    msg!("Start NAV per share: {}", fund.start_nav_per_share);
+    msg!("High-water mark: {}", fund.high_water_mark);
     msg!("Trading ends at: {}", fund.trading_end_ts);
 
     Ok(())
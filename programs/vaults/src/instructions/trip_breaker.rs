@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::fees::MgmtFeeAccounts;
+use crate::nav::fund_nav;
+use crate::state::{FundState, FundStage, ProtocolConfig, FUND_SEED, VAULT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED};
+use crate::errors::FundError;
+
+/// Trip the drawdown circuit breaker
+/// Anyone can call once NAV per share is below the fund's drawdown floor.
+/// NAV accounts (Position, MarketPrice pairs) are passed via remaining_accounts.
+#[derive(Accounts)]
+pub struct TripBreaker<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [FUND_SEED, &fund_state.fund_id.to_le_bytes()],
+        bump = fund_state.bump,
+        constraint = fund_state.stage == FundStage::Trading @ FundError::InvalidStage
+    )]
+    pub fund_state: Account<'info, FundState>,
+
+    /// Vault's USDC token account
+    #[account(
+        constraint = vault_usdc_ata.key() == fund_state.vault_usdc_ata
+    )]
+    pub vault_usdc_ata: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
+        bump = fund_state.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Share mint (accrued management fee shares are minted here)
+    #[account(
+        mut,
+        constraint = share_mint.key() == fund_state.share_mint @ FundError::InvalidShareMint
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<TripBreaker>) -> Result<()> {
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
+    let clock = Clock::get()?;
+    let vault_usdc = ctx.accounts.vault_usdc_ata.amount;
+    let nav = fund_nav(
+        &ctx.accounts.fund_state,
+        vault_usdc,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+        ctx.accounts.protocol_config.max_price_staleness_secs,
+    )?;
+
+    let fund = &mut ctx.accounts.fund_state;
+    require!(fund.drawdown_breached(nav), FundError::DrawdownNotBreached);
+
+    // Halt trading - manager must unwind positions in Settlement
+    fund.breaker_tripped = true;
+    fund.stage = FundStage::Settlement;
+
+    msg!("=== DRAWDOWN BREAKER TRIPPED ===");
+    msg!("NAV: {} USDC", nav);
+    msg!("NAV per share: {}", fund.nav_per_share(nav));
+    msg!("Drawdown floor: {}", fund.drawdown_floor_per_share());
+    msg!("Fund moved to Settlement");
+
+    Ok(())
+}
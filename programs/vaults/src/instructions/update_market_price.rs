@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    MarketPrice, ProtocolConfig,
+    PROTOCOL_CONFIG_SEED, MARKET_PRICE_SEED, MAX_OUTCOME_PRICE,
+};
+use crate::errors::FundError;
+
+/// Publish the mark price of an outcome token
+/// Oracle authority only - creates the MarketPrice PDA on first update
+/// Never paused: NAV must stay priceable while funds wind down
+#[derive(Accounts)]
+#[instruction(outcome_mint: Pubkey)]
+pub struct UpdateMarketPrice<'info> {
+    #[account(
+        mut,
+        constraint = oracle.key() == protocol_config.oracle_authority @ FundError::UnauthorizedOracle
+    )]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = MarketPrice::LEN,
+        seeds = [MARKET_PRICE_SEED, outcome_mint.as_ref()],
+        bump
+    )]
+    pub market_price: Account<'info, MarketPrice>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateMarketPrice>,
+    outcome_mint: Pubkey,
+    price: u64,
+    confidence: u64,
+) -> Result<()> {
+    require!(price <= MAX_OUTCOME_PRICE, FundError::InvalidPrice);
+
+    let clock = Clock::get()?;
+    let market_price = &mut ctx.accounts.market_price;
+
+    market_price.outcome_mint = outcome_mint;
+    market_price.price = price;
+    market_price.confidence = confidence;
+    market_price.updated_at = clock.unix_timestamp;
+    market_price.bump = ctx.bumps.market_price;
+
+    msg!("Market price updated: {}", outcome_mint);
+    msg!("Price: {} (confidence: {})", price, confidence);
+
+    Ok(())
+}
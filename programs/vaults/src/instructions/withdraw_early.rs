@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount, Burn, Transfer, burn, transfer};
 
+use crate::fees::MgmtFeeAccounts;
+use crate::nav::fund_nav;
 use crate::state::{
-    FundState, FundStage, WithdrawalRequest, RequestStatus,
-    FUND_SEED, VAULT_AUTHORITY_SEED, WITHDRAWAL_REQUEST_SEED,
+    require_not_paused, FundState, FundStage, ProtocolConfig,
+    FUND_SEED, VAULT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED, PAUSE_WITHDRAWALS,
 };
 use crate::errors::FundError;
 
@@ -22,6 +24,12 @@ pub struct WithdrawEarly<'info> {
     )]
     pub fund_state: Box<Account<'info, FundState>>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// CHECK: Vault authority PDA
     #[account(
         seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
@@ -59,13 +67,32 @@ pub struct WithdrawEarly<'info> {
     )]
     pub share_mint: Account<'info, Mint>,
 
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<WithdrawEarly>, shares: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_WITHDRAWALS)?;
+
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
     let fund = &ctx.accounts.fund_state;
     let vault_usdc = ctx.accounts.vault_usdc_ata.amount;
+    let clock = Clock::get()?;
     
     require!(shares > 0, FundError::ZeroWithdrawal);
     require!(
@@ -74,7 +101,14 @@ pub fn handler(ctx: Context<WithdrawEarly>, shares: u64) -> Result<()> {
     );
 
     // Calculate share value (NAV per share * shares)
-    let share_value = fund.usdc_for_shares(shares, vault_usdc);
+    let nav = fund_nav(
+        fund,
+        vault_usdc,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+        ctx.accounts.protocol_config.max_price_staleness_secs,
+    )?;
+    let share_value = fund.usdc_for_shares(shares, nav);
     
     // Calculate early exit fee (5% default)
     let exit_fee = fund.calculate_early_exit_fee(share_value);
@@ -82,10 +116,12 @@ pub fn handler(ctx: Context<WithdrawEarly>, shares: u64) -> Result<()> {
     
     // Check if buffer can cover this withdrawal
     // Buffer check: after withdrawal, vault should still have min_buffer % of remaining NAV
-    let post_withdrawal_nav = vault_usdc.saturating_sub(payout);
+    // (USDC owed to the manager as performance fee is not part of the buffer)
+    let post_withdrawal_nav = nav.saturating_sub(payout);
     let min_buffer_needed = fund.min_buffer_amount(post_withdrawal_nav);
+    let free_usdc = vault_usdc.saturating_sub(fund.outstanding_perf_fee());
     
-    let buffer_sufficient = vault_usdc >= payout + min_buffer_needed;
+    let buffer_sufficient = free_usdc >= payout + min_buffer_needed;
     
     if buffer_sufficient {
         // === Instant withdrawal from buffer ===
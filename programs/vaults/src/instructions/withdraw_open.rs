@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Burn, Transfer};
 
-use crate::state::{FundState, FundStage, FUND_SEED, VAULT_AUTHORITY_SEED, SHARE_MINT_SEED};
+use crate::fees::MgmtFeeAccounts;
+use crate::nav::fund_nav;
+use crate::state::{
+    require_not_paused, FundState, FundStage, ProtocolConfig,
+    FUND_SEED, VAULT_AUTHORITY_SEED, SHARE_MINT_SEED, PROTOCOL_CONFIG_SEED, PAUSE_WITHDRAWALS,
+};
 use crate::errors::FundError;
 
 #[derive(Accounts)]
@@ -17,6 +22,12 @@ pub struct WithdrawOpen<'info> {
     )]
     pub fund_state: Account<'info, FundState>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// CHECK: PDA for vault authority
     #[account(
         seeds = [VAULT_AUTHORITY_SEED, fund_state.key().as_ref()],
@@ -53,10 +64,28 @@ pub struct WithdrawOpen<'info> {
     )]
     pub investor_share_ata: Account<'info, TokenAccount>,
 
+    /// Management fee share escrow
+    #[account(
+        mut,
+        constraint = mgmt_fee_escrow.key() == fund_state.mgmt_fee_escrow @ FundError::InvalidFeeEscrow
+    )]
+    pub mgmt_fee_escrow: Box<Account<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
 }
 
 pub fn handler(ctx: Context<WithdrawOpen>, shares: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.protocol_config, &ctx.accounts.fund_state, PAUSE_WITHDRAWALS)?;
+
+    // Accrue the management fee before anything reads total_shares
+    MgmtFeeAccounts {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        share_mint: ctx.accounts.share_mint.to_account_info(),
+        mgmt_fee_escrow: ctx.accounts.mgmt_fee_escrow.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+    }
+    .accrue(&mut ctx.accounts.fund_state, Clock::get()?.unix_timestamp)?;
+
     require!(shares > 0, FundError::ZeroWithdrawal);
     require!(
         ctx.accounts.investor_share_ata.amount >= shares,
@@ -65,11 +94,19 @@ pub fn handler(ctx: Context<WithdrawOpen>, shares: u64) -> Result<()> {
 
     let fund = &mut ctx.accounts.fund_state;
     
-    // Get current vault balance
+    // Get current vault balance and NAV
+    let clock = Clock::get()?;
     let vault_balance = ctx.accounts.vault_usdc_ata.amount;
+    let nav = fund_nav(
+        fund,
+        vault_balance,
+        ctx.remaining_accounts,
+        clock.unix_timestamp,
+        ctx.accounts.protocol_config.max_price_staleness_secs,
+    )?;
     
     // Calculate USDC to return
-    let usdc_amount = fund.usdc_for_shares(shares, vault_balance);
+    let usdc_amount = fund.usdc_for_shares(shares, nav);
     
     require!(
         vault_balance >= usdc_amount,
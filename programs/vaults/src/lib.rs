@@ -5,24 +5,81 @@ declare_id!("LkLi5oLN8TG7EW95N4fMGxmHv6R9HyHGUqvQrHDoFWH");
 pub mod state;
 pub mod instructions;
 pub mod errors;
+pub mod dflow;
+pub mod nav;
+pub mod fees;
+pub mod events;
 
 use instructions::*;
+use dflow::DFlowInstruction;
+use state::{MarketCategory, MarketStatus, WithdrawalPricing};
 
 #[program]
 pub mod vaults {
     use super::*;
 
     /// Initialize protocol configuration
-    /// Admin only - sets global parameters and whitelisted programs
+    /// Admin only - sets global parameters, fund risk bounds and whitelisted programs
+    /// (a zero epoch interval uses DEFAULT_EPOCH_INTERVAL_SECS)
     pub fn initialize_protocol(
         ctx: Context<InitializeProtocol>,
         allowed_dflow_program: Pubkey,
+        oracle_authority: Pubkey,
+        max_early_exit_fee_bps: u16,
+        min_buffer_bps: u16,
+        default_epoch_interval_secs: i64,
     ) -> Result<()> {
-        instructions::initialize_protocol::handler(ctx, allowed_dflow_program)
+        instructions::initialize_protocol::handler(
+            ctx,
+            allowed_dflow_program,
+            oracle_authority,
+            max_early_exit_fee_bps,
+            min_buffer_bps,
+            default_epoch_interval_secs,
+        )
+    }
+
+    /// Update protocol configuration (fee caps, fund risk bounds, price
+    /// staleness, DFlow program, fee recipient, oracle)
+    /// Admin only - bounded by hard caps, every change is logged; the USDC
+    /// mint is fixed at initialization
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        update: ProtocolConfigUpdate,
+    ) -> Result<()> {
+        instructions::protocol_admin::handler_update_config(ctx, update)
+    }
+
+    /// Propose a new protocol admin
+    /// Admin only - takes effect once the new admin calls accept_admin
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::protocol_admin::handler_propose_admin(ctx, new_admin)
+    }
+
+    /// Accept a pending protocol admin proposal
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::protocol_admin::handler_accept_admin(ctx)
+    }
+
+    /// Set the protocol-wide pause bitmask (deposits, withdrawals, trading,
+    /// epoch processing, redemptions)
+    /// Admin only
+    pub fn set_protocol_pause(ctx: Context<SetProtocolPause>, paused: u8) -> Result<()> {
+        instructions::pause::handler_set_protocol_pause(ctx, paused)
+    }
+
+    /// Set a fund's pause bitmask
+    /// Manager or admin - only the admin can pause redemptions
+    pub fn set_fund_pause(ctx: Context<SetFundPause>, paused: u8) -> Result<()> {
+        instructions::pause::handler_set_fund_pause(ctx, paused)
     }
 
     /// Create a new prediction fund (step 1)
-    /// Manager specifies fees, name, symbol, and trading window
+    /// Manager specifies fees (incl. annual management fee), name, symbol,
+    /// liquidity profile (early exit fee, buffer, epoch interval; 0 = protocol
+    /// default interval), trading window, drawdown limit, how queued withdrawals are priced and
+    /// the per-epoch redemption gate
+    #[allow(clippy::too_many_arguments)]
     pub fn create_fund(
         ctx: Context<CreateFund>,
         fund_id: u64,
@@ -30,8 +87,15 @@ pub mod vaults {
         symbol: String,
         deposit_fee_bps: u16,
         perf_fee_bps: u16,
+        mgmt_fee_bps: u16,
+        early_exit_fee_bps: u16,
+        liquidity_buffer_bps: u16,
+        epoch_interval_secs: i64,
         trading_start_ts: i64,
         trading_end_ts: i64,
+        max_drawdown_bps: u16,
+        withdrawal_pricing: WithdrawalPricing,
+        max_epoch_redemption_bps: u16,
     ) -> Result<()> {
         instructions::create_fund::handler(
             ctx,
@@ -40,8 +104,15 @@ pub mod vaults {
             symbol,
             deposit_fee_bps,
             perf_fee_bps,
+            mgmt_fee_bps,
+            early_exit_fee_bps,
+            liquidity_buffer_bps,
+            epoch_interval_secs,
             trading_start_ts,
             trading_end_ts,
+            max_drawdown_bps,
+            withdrawal_pricing,
+            max_epoch_redemption_bps,
         )
     }
 
@@ -52,17 +123,43 @@ pub mod vaults {
     }
 
     /// Initialize vault accounts (step 2b)
-    /// Creates vault USDC ATA, manager fee ATA
+    /// Creates vault USDC ATA, fee escrow, management fee escrow and fee ledger
     pub fn initialize_vault_accounts(ctx: Context<InitializeVaultAccounts>) -> Result<()> {
         instructions::create_fund::handler_init_vault_accounts(ctx)
     }
 
+    /// Attach the fund's strategy mandate (categories, outcome mints,
+    /// exposure and position limits)
+    /// Manager only, Open stage only - immutable once trading starts
+    pub fn set_mandate(ctx: Context<SetMandate>, params: MandateParams) -> Result<()> {
+        instructions::set_mandate::handler(ctx, params)
+    }
+
     /// Deposit USDC into a fund during Open stage
-    /// Deposit fee is charged immediately and sent to manager
+    /// Deposit fee is charged immediately and sent to manager (less the
+    /// protocol share)
+    /// Shares are priced at NAV (see `nav::fund_nav` for remaining_accounts)
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         instructions::deposit::handler(ctx, amount)
     }
 
+    /// Subscribe during Trading stage
+    /// USDC is escrowed until the current epoch is struck
+    pub fn request_deposit(ctx: Context<RequestDeposit>, amount: u64) -> Result<()> {
+        instructions::request_deposit::handler(ctx, amount)
+    }
+
+    /// Cancel a deposit request before its epoch is struck
+    pub fn cancel_deposit(ctx: Context<CancelDeposit>, epoch: u64) -> Result<()> {
+        instructions::cancel_deposit::handler(ctx, epoch)
+    }
+
+    /// Mint shares for struck deposit requests at the latest strike's NAV per share
+    /// remaining_accounts: (DepositRequest, investor share account, investor) triplets
+    pub fn process_deposits<'info>(ctx: Context<'_, '_, '_, 'info, ProcessDeposits<'info>>) -> Result<()> {
+        instructions::process_deposits::handler(ctx)
+    }
+
     /// Withdraw USDC from a fund during Open stage
     /// Burns shares and returns proportional USDC
     pub fn withdraw_open(ctx: Context<WithdrawOpen>, shares: u64) -> Result<()> {
@@ -78,30 +175,40 @@ pub mod vaults {
 
     /// Request a withdrawal during Trading stage (queue-based)
     /// Creates a withdrawal request PDA processed at next epoch
+    /// Requested shares are escrowed until burned (filled) or returned (cancelled)
     pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, shares: u64) -> Result<()> {
         instructions::request_withdrawal::handler(ctx, shares)
     }
 
     /// Cancel a pending withdrawal request
-    /// Only allowed if not partially filled
-    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>, request_index: u32) -> Result<()> {
-        instructions::cancel_withdrawal::handler(ctx, request_index)
+    /// Only allowed if not partially filled (any active request once Closed) -
+    /// the unfilled escrowed shares are returned
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>, sequence: u64) -> Result<()> {
+        instructions::cancel_withdrawal::handler(ctx, sequence)
     }
 
-    /// Process a single withdrawal request from the queue
-    /// Can be called after epoch interval (24h default)
-    pub fn process_single_withdrawal(
-        ctx: Context<ProcessSingleWithdrawal>,
-        request_index: u32,
-    ) -> Result<()> {
-        instructions::process_epoch::handler_process_single(ctx, request_index)
+    /// Close a completed or cancelled withdrawal request behind the queue head
+    /// Permissionless - rent goes back to the investor
+    pub fn close_withdrawal_request(ctx: Context<CloseWithdrawalRequest>, sequence: u64) -> Result<()> {
+        instructions::close_withdrawal_request::handler(ctx, sequence)
     }
 
-    /// Trigger epoch processing (updates last_epoch_ts)
-    pub fn process_epoch(ctx: Context<ProcessEpoch>) -> Result<()> {
+    /// Strike the epoch (NAV, pending shares, redemption budget) and fill the
+    /// first batch of withdrawal requests pro rata
+    /// remaining_accounts: NAV pairs, then (WithdrawalRequest, investor USDC) pairs
+    pub fn process_epoch<'info>(ctx: Context<'_, '_, '_, 'info, ProcessEpoch<'info>>) -> Result<()> {
         instructions::process_epoch::handler(ctx)
     }
 
+    /// Fill a further batch of withdrawal requests with the latest epoch's
+    /// pro-rata ratio
+    /// remaining_accounts: (WithdrawalRequest, investor USDC) pairs
+    pub fn process_withdrawal_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProcessWithdrawalBatch<'info>>,
+    ) -> Result<()> {
+        instructions::process_epoch::handler_process_batch(ctx)
+    }
+
     /// Transition fund from Open to Trading stage
     /// Locks initial AUM for performance fee calculation
     pub fn start_trading(ctx: Context<StartTrading>) -> Result<()> {
@@ -109,12 +216,21 @@ pub mod vaults {
     }
 
     /// Execute a trade on DFlow prediction markets
-    /// Manager only - validates and executes trade instructions
-    pub fn execute_trade(
-        ctx: Context<ExecuteTrade>,
+    /// Manager only - market must be in the protocol allowlist; validates the
+    /// DFlow instruction bundle and executes it via CPI signed by the vault authority
+    pub fn execute_trade<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteTrade<'info>>,
         params: TradeParams,
+        dflow_instructions: Vec<DFlowInstruction>,
     ) -> Result<()> {
-        instructions::execute_trade::handler(ctx, params)
+        instructions::execute_trade::handler(ctx, params, dflow_instructions)
+    }
+
+    /// Trip the drawdown circuit breaker
+    /// Permissionless - moves a Trading fund whose NAV per share is below its
+    /// drawdown floor into Settlement early
+    pub fn trip_breaker(ctx: Context<TripBreaker>) -> Result<()> {
+        instructions::trip_breaker::handler(ctx)
     }
 
     /// Transition fund from Trading to Settlement stage
@@ -124,15 +240,108 @@ pub mod vaults {
     }
 
     /// Finalize fund and transition to Closed stage
-    /// Calculates performance fee based on profit
-    /// Requires vault to hold only USDC (all positions closed)
-    pub fn finalize_close(ctx: Context<FinalizeClose>) -> Result<()> {
+    /// Crystallizes the performance fee above the high-water mark and records
+    /// a SeasonSummary
+    /// Requires all positions to be flat (every open Position account passed
+    /// in remaining_accounts)
+    pub fn finalize_close<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeClose<'info>>,
+    ) -> Result<()> {
         instructions::finalize_close::handler(ctx)
     }
 
+    /// Close a flat position record
+    /// Permissionless - rent goes back to the manager and the fund's
+    /// position count drops
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        instructions::close_position::handler(ctx)
+    }
+
     /// Redeem shares for USDC during Closed stage
     /// First redemption pays performance fee to manager
     pub fn redeem(ctx: Context<Redeem>, shares: u64) -> Result<()> {
         instructions::redeem::handler(ctx, shares)
     }
+
+    /// Redeem resolved-market outcome tokens from the vault into USDC
+    /// Permissionless once the market is resolved - a single CPI into the
+    /// whitelisted venue's redemption path; the winning mint must pay par
+    /// and the matching position is realized
+    pub fn redeem_outcome<'info>(
+        ctx: Context<'_, '_, '_, 'info, RedeemOutcome<'info>>,
+        redemption_instructions: Vec<DFlowInstruction>,
+    ) -> Result<()> {
+        instructions::redeem_outcome::handler(ctx, redemption_instructions)
+    }
+
+    /// Roll a Closed fund into a new season (Closed -> Open)
+    /// Manager only - shareholders stay in and the fee baseline resets to NAV
+    /// Requires all positions closed and no withdrawal shares in escrow
+    pub fn roll_season(
+        ctx: Context<RollSeason>,
+        trading_start_ts: i64,
+        trading_end_ts: i64,
+    ) -> Result<()> {
+        instructions::roll_season::handler(ctx, trading_start_ts, trading_end_ts)
+    }
+
+    /// Mint accrued management fee shares to the manager
+    /// Manager only - the fee accrues by dilution on every state change
+    pub fn collect_management_fee(ctx: Context<CollectManagementFee>) -> Result<()> {
+        instructions::collect_management_fee::handler(ctx)
+    }
+
+    /// Withdraw the manager's accrued deposit and performance fees
+    /// Manager only - to any USDC account the manager signs for
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        instructions::claim_fees::handler_claim_fees(ctx)
+    }
+
+    /// Withdraw the protocol's accrued fee share from a fund
+    /// Protocol fee recipient only
+    pub fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+        instructions::claim_fees::handler_claim_protocol_fees(ctx)
+    }
+
+    /// List a market in the protocol allowlist
+    /// Admin only
+    pub fn add_market(
+        ctx: Context<AddMarket>,
+        market_ledger: Pubkey,
+        yes_mint: Pubkey,
+        no_mint: Pubkey,
+        category: MarketCategory,
+    ) -> Result<()> {
+        instructions::manage_markets::handler_add_market(ctx, market_ledger, yes_mint, no_mint, category)
+    }
+
+    /// Suspend or reactivate a listed market
+    /// Admin only - suspended markets only accept position-reducing sells
+    pub fn set_market_status(ctx: Context<SetMarketStatus>, status: MarketStatus) -> Result<()> {
+        instructions::manage_markets::handler_set_market_status(ctx, status)
+    }
+
+    /// Resolve a listed market (winning side: 0 = Yes, 1 = No)
+    /// Admin only - resolution is final and enables outcome redemption
+    pub fn resolve_market(ctx: Context<ResolveMarket>, winning_side: u8) -> Result<()> {
+        instructions::manage_markets::handler_resolve_market(ctx, winning_side)
+    }
+
+    /// Delist a market from the protocol allowlist
+    /// Admin only - refused while funds hold positions in the market; rent is
+    /// returned to the admin
+    pub fn remove_market(ctx: Context<RemoveMarket>) -> Result<()> {
+        instructions::manage_markets::handler_remove_market(ctx)
+    }
+
+    /// Publish the mark price of an outcome token used for NAV
+    /// Oracle authority only
+    pub fn update_market_price(
+        ctx: Context<UpdateMarketPrice>,
+        outcome_mint: Pubkey,
+        price: u64,
+        confidence: u64,
+    ) -> Result<()> {
+        instructions::update_market_price::handler(ctx, outcome_mint, price, confidence)
+    }
 }
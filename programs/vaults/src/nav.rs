@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{load_account, FundState, MarketPrice, Position, MAX_OUTCOME_PRICE};
+use crate::errors::FundError;
+
+/// Net asset value of a fund in USDC
+///
+/// NAV = vault USDC + sum(position quantity * mark price) for every open
+/// position - performance fee crystallized but not yet paid.
+/// `nav_accounts` must hold exactly one (Position, MarketPrice) pair per open
+/// position (`fund.open_position_count`); prices older than
+/// `max_staleness_secs` are rejected.
+pub fn fund_nav(
+    fund: &Account<FundState>,
+    vault_usdc: u64,
+    nav_accounts: &[AccountInfo],
+    now: i64,
+    max_staleness_secs: i64,
+) -> Result<u64> {
+    require!(
+        nav_accounts.len() == 2 * fund.open_position_count as usize,
+        FundError::NavAccountsMismatch
+    );
+
+    let fund_key = fund.key();
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(fund.open_position_count as usize);
+    let mut nav = vault_usdc as u128;
+
+    for pair in nav_accounts.chunks(2) {
+        let position: Position = load_account(&pair[0])?;
+        require_keys_eq!(position.fund, fund_key, FundError::NavAccountsMismatch);
+        require!(position.is_open(), FundError::NavAccountsMismatch);
+        require!(!seen.contains(pair[0].key), FundError::NavAccountsMismatch);
+        seen.push(pair[0].key());
+
+        let market_price: MarketPrice = load_account(&pair[1])?;
+        require_keys_eq!(
+            market_price.outcome_mint,
+            position.outcome_mint,
+            FundError::NavAccountsMismatch
+        );
+        let price = market_price.checked_price(now, max_staleness_secs)?;
+
+        nav += (position.quantity as u128) * (price as u128) / (MAX_OUTCOME_PRICE as u128);
+    }
+
+    // The unpaid fee still sits in the vault but belongs to the manager
+    let nav = nav.saturating_sub(fund.outstanding_perf_fee() as u128);
+
+    u64::try_from(nav).map_err(|_| FundError::MathOverflow.into())
+}
+
+/// Split remaining accounts into the leading NAV pairs and the rest
+pub fn split_nav_accounts<'a, 'info>(
+    fund: &FundState,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let nav_len = 2 * fund.open_position_count as usize;
+    require!(remaining_accounts.len() >= nav_len, FundError::NavAccountsMismatch);
+    Ok(remaining_accounts.split_at(nav_len))
+}
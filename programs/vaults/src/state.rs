@@ -1,41 +1,115 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::FundError;
+
 /// Fund lifecycle stages
 /// Open: Deposits allowed, no trading
 /// Trading: Deposits locked, manager can trade
 /// Settlement: No new trades, manager closes positions
 /// Closed: Investors can redeem shares
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum FundStage {
+    #[default]
     Open,
     Trading,
     Settlement,
     Closed,
 }
 
-impl Default for FundStage {
-    fn default() -> Self {
-        FundStage::Open
-    }
-}
-
 /// Withdrawal request status
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum RequestStatus {
+    #[default]
     Pending,
     PartiallyFilled,
     Completed,
     Cancelled,
 }
 
-impl Default for RequestStatus {
-    fn default() -> Self {
-        RequestStatus::Pending
+/// How queued withdrawals are priced
+/// ForwardEpochNav: at the NAV per share struck when the filling epoch closes
+/// LockedAtRequest: at the NAV per share recorded when the request was made
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum WithdrawalPricing {
+    #[default]
+    ForwardEpochNav,
+    LockedAtRequest,
+}
+
+/// Outcome side of a binary prediction market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Side {
+    Yes,
+    No,
+}
+
+impl TryFrom<u8> for Side {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Side::Yes),
+            1 => Ok(Side::No),
+            _ => err!(FundError::InvalidSide),
+        }
+    }
+}
+
+/// Trade direction
+/// Buy: USDC -> outcome tokens
+/// Sell: outcome tokens -> USDC
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Buy,
+    Sell,
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Direction::Buy),
+            1 => Ok(Direction::Sell),
+            _ => err!(FundError::InvalidDirection),
+        }
+    }
+}
+
+/// Market category used by the allowlist and fund mandates
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MarketCategory {
+    Crypto,
+    Economics,
+    Sports,
+    Politics,
+    Other,
+}
+
+impl MarketCategory {
+    /// Bit of this category in a category bitmask
+    pub fn mask(self) -> u8 {
+        1 << (self as u8)
     }
 }
 
+/// Allowlist status of a market
+/// Active: funds may trade it
+/// Suspended: new exposure blocked (e.g. market being gamed), sells allowed
+/// Resolved: market settled, only the winning mint redeems at par
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MarketStatus {
+    Active,
+    Suspended,
+    Resolved,
+}
+
 /// Protocol-level configuration account
 /// Controls global parameters and whitelisted programs
+/// Fields added after the initial layout are appended after `bump`.
+/// There is no realloc migration: `LEN` covers the appended fields, so configs
+/// created by the initial program are too short to load and an upgrade needs
+/// fresh state (re-run initialize_protocol).
 #[account]
 pub struct ProtocolConfig {
     /// Admin who can update config
@@ -54,10 +128,22 @@ pub struct ProtocolConfig {
     pub allowed_dflow_program: Pubkey,
     /// USDC mint address
     pub usdc_mint: Pubkey,
-    /// Protocol fee recipient (optional future use)
+    /// Key allowed to claim the protocol fee share from fund fee ledgers
     pub protocol_fee_recipient: Pubkey,
     /// PDA bump seed
     pub bump: u8,
+    /// Oracle key allowed to publish MarketPrice accounts
+    pub oracle_authority: Pubkey,
+    /// Maximum annual management fee (200 = 2%)
+    pub max_mgmt_fee_bps: u16,
+    /// Admin proposed by propose_admin (default = none)
+    pub pending_admin: Pubkey,
+    /// Protocol-wide pause bitmask (PAUSE_* flags, admin only)
+    pub paused: u8,
+    /// Protocol share of deposit and performance fees in bps
+    pub protocol_fee_bps: u16,
+    /// Maximum age of a MarketPrice used for NAV
+    pub max_price_staleness_secs: i64,
 }
 
 impl ProtocolConfig {
@@ -71,11 +157,44 @@ impl ProtocolConfig {
         32 + // allowed_dflow_program
         32 + // usdc_mint
         32 + // protocol_fee_recipient
-        1;   // bump
+        1 +  // bump
+        32 + // oracle_authority
+        2 +  // max_mgmt_fee_bps
+        32 + // pending_admin
+        1 +  // paused
+        2 +  // protocol_fee_bps
+        8;   // max_price_staleness_secs
+
+    /// Protocol share of a manager fee
+    pub fn protocol_share(&self, fee: u64) -> u64 {
+        ((fee as u128) * (self.protocol_fee_bps as u128) / 10_000) as u64
+    }
+
+    /// Check the fund risk bounds against the hard limits
+    pub fn validate_risk_bounds(&self) -> Result<()> {
+        require!(
+            self.max_early_exit_fee_bps <= HARD_MAX_EARLY_EXIT_FEE_BPS,
+            FundError::InvalidProtocolConfig
+        );
+        require!(self.min_buffer_bps <= 10_000, FundError::InvalidProtocolConfig);
+        require!(
+            (MIN_EPOCH_INTERVAL_SECS..=MAX_EPOCH_INTERVAL_SECS).contains(&self.default_epoch_interval_secs),
+            FundError::InvalidEpochInterval
+        );
+        require!(
+            (MIN_PRICE_STALENESS_SECS..=MAX_PRICE_STALENESS_SECS).contains(&self.max_price_staleness_secs),
+            FundError::InvalidProtocolConfig
+        );
+        Ok(())
+    }
 }
 
 /// Fund state account - one per fund
 /// Stores all fund configuration, lifecycle, and financial data
+/// Fields added after the initial layout are appended after `share_mint_bump`
+/// so existing accounts keep their offsets. There is no realloc migration:
+/// funds created by the initial program are shorter than `LEN` and cannot be
+/// loaded, so an upgrade needs fresh state (funds are recreated).
 #[account]
 pub struct FundState {
     /// Unique fund identifier
@@ -114,13 +233,13 @@ pub struct FundState {
     pub last_epoch_ts: i64,
     /// Epoch interval in seconds (default 86400 = 24h)
     pub epoch_interval_secs: i64,
-    /// Number of pending withdrawal requests
-    pub pending_request_count: u32,
+    /// Unused - former request counter, superseded by the WithdrawalQueue
+    pub reserved_request_count: u32,
     
     // === Financial Tracking ===
     /// Initial AUM in USDC (locked at Trading start)
     pub initial_aum_usdc: u64,
-    /// Performance fee due in USDC (calculated at finalize)
+    /// Performance fee crystallized in USDC (at epoch strikes and finalize)
     pub perf_fee_due_usdc: u64,
     /// Whether performance fee has been paid
     pub perf_fee_paid: bool,
@@ -134,8 +253,8 @@ pub struct FundState {
     pub vault_authority: Pubkey,
     /// Vault's USDC token account
     pub vault_usdc_ata: Pubkey,
-    /// Manager's fee receiving USDC account
-    pub manager_fee_ata: Pubkey,
+    /// Unused - fees now accrue to `fee_escrow`
+    pub reserved_manager_fee_ata: Pubkey,
     /// USDC mint address
     pub usdc_mint: Pubkey,
     
@@ -150,6 +269,75 @@ pub struct FundState {
     pub vault_authority_bump: u8,
     /// Share mint PDA bump
     pub share_mint_bump: u8,
+
+    // === Positions ===
+    /// Number of positions with a non-zero quantity
+    pub open_position_count: u16,
+    /// Number of Position accounts created for this fund
+    pub position_count: u16,
+
+    // === Strategy Mandate ===
+    /// Whether a FundMandate constrains execute_trade
+    pub has_mandate: bool,
+
+    // === Drawdown Circuit Breaker ===
+    /// Max drawdown of NAV per share from trading start in bps (0 = disabled)
+    pub max_drawdown_bps: u16,
+    /// NAV per share (1e6 scale) snapshotted at trading start
+    pub start_nav_per_share: u64,
+    /// Whether the breaker moved the fund to Settlement early
+    pub breaker_tripped: bool,
+
+    // === Epochs ===
+    /// Epoch currently accepting requests (EpochState is struck at its close)
+    pub current_epoch: u64,
+    /// Pricing mode for queued withdrawals
+    pub withdrawal_pricing: WithdrawalPricing,
+    /// Max USDC redeemed per epoch as bps of NAV (0 = no gate)
+    pub max_epoch_redemption_bps: u16,
+
+    // === Subscription Queue ===
+    /// USDC escrowed in deposit requests awaiting their epoch strike
+    pub pending_deposit_usdc: u64,
+
+    // === Seasons ===
+    /// Current season (starts at 1, incremented by roll_season)
+    pub season: u32,
+    /// NAV per share (1e6 scale) above which performance fees accrue
+    pub high_water_mark: u64,
+
+    // === Management Fee ===
+    /// Annual management fee in bps, accrued by minting shares to the manager
+    pub mgmt_fee_bps: u16,
+    /// Unix timestamp of the last management fee accrual
+    pub last_mgmt_accrual_ts: i64,
+    /// Management fee shares accrued (counted in total_shares) but not yet
+    /// minted - only non-zero inside a handler, before MgmtFeeAccounts mints them
+    pub unminted_mgmt_fee_shares: u64,
+
+    // === Emergency Pause ===
+    /// Pause bitmask set by the manager (PAUSE_* flags, never PAUSE_REDEMPTIONS)
+    pub paused: u8,
+    /// Pause bitmask set by the protocol admin
+    pub admin_paused: u8,
+
+    // === Fee Ledger ===
+    /// Fee escrow USDC account (vault authority owned, backs the FeeLedger)
+    pub fee_escrow: Pubkey,
+
+    // === Withdrawal Queue (cont.) ===
+    /// Value of pending withdrawal shares at their request-time NAV per share
+    /// (the redemption demand under LockedAtRequest pricing)
+    pub pending_withdrawal_locked_usdc: u64,
+
+    // === Seasons (cont.) ===
+    /// First epoch of the current season (earlier epochs are never processed)
+    pub season_start_epoch: u64,
+
+    // === Management Fee (cont.) ===
+    /// Share account (vault authority owned) holding minted management fee
+    /// shares until the manager collects them
+    pub mgmt_fee_escrow: Pubkey,
 }
 
 impl FundState {
@@ -168,7 +356,7 @@ impl FundState {
         8 +   // pending_withdrawal_shares
         8 +   // last_epoch_ts
         8 +   // epoch_interval_secs
-        4 +   // pending_request_count
+        4 +   // reserved_request_count
         8 +   // initial_aum_usdc
         8 +   // perf_fee_due_usdc
         1 +   // perf_fee_paid
@@ -176,28 +364,59 @@ impl FundState {
         32 +  // share_mint
         32 +  // vault_authority
         32 +  // vault_usdc_ata
-        32 +  // manager_fee_ata
+        32 +  // reserved_manager_fee_ata
         32 +  // usdc_mint
         8 +   // total_shares
         1 +   // bump
         1 +   // vault_authority_bump
-        1;    // share_mint_bump
+        1 +   // share_mint_bump
+        2 +   // open_position_count
+        2 +   // position_count
+        1 +   // has_mandate
+        2 +   // max_drawdown_bps
+        8 +   // start_nav_per_share
+        1 +   // breaker_tripped
+        8 +   // current_epoch
+        1 +   // withdrawal_pricing (enum)
+        2 +   // max_epoch_redemption_bps
+        8 +   // pending_deposit_usdc
+        4 +   // season
+        8 +   // high_water_mark
+        2 +   // mgmt_fee_bps
+        8 +   // last_mgmt_accrual_ts
+        8 +   // unminted_mgmt_fee_shares
+        1 +   // paused
+        1 +   // admin_paused
+        32 +  // fee_escrow
+        8 +   // pending_withdrawal_locked_usdc
+        8 +   // season_start_epoch
+        32;   // mgmt_fee_escrow
 
     /// Calculate shares to mint for a deposit (after fees)
-    pub fn shares_for_deposit(&self, net_amount: u64, vault_balance: u64) -> u64 {
+    /// Fails if shares exist but NAV is zero - they cannot be priced
+    pub fn shares_for_deposit(&self, net_amount: u64, nav: u64) -> Result<u64> {
         if self.total_shares == 0 {
-            net_amount
-        } else {
-            ((net_amount as u128) * (self.total_shares as u128) / (vault_balance as u128)) as u64
+            return Ok(net_amount);
         }
+        require!(nav > 0, FundError::ZeroNav);
+        Ok(((net_amount as u128) * (self.total_shares as u128) / (nav as u128)) as u64)
     }
 
-    /// Calculate USDC to return for shares burned
-    pub fn usdc_for_shares(&self, shares: u64, vault_balance: u64) -> u64 {
+    /// Calculate USDC value of shares at the given NAV
+    pub fn usdc_for_shares(&self, shares: u64, nav: u64) -> u64 {
         if self.total_shares == 0 {
             0
         } else {
-            ((shares as u128) * (vault_balance as u128) / (self.total_shares as u128)) as u64
+            ((shares as u128) * (nav as u128) / (self.total_shares as u128)) as u64
+        }
+    }
+
+    /// NAV per share scaled by 1e6 (1 USDC per share when no shares exist)
+    pub fn nav_per_share(&self, nav: u64) -> u64 {
+        if self.total_shares > 0 {
+            ((nav as u128) * 1_000_000 / (self.total_shares as u128)) as u64
+        } else {
+            1_000_000
         }
     }
 
@@ -211,6 +430,93 @@ impl FundState {
         ((profit as u128) * (self.perf_fee_bps as u128) / 10_000) as u64
     }
 
+    /// Accrue the management fee for the time elapsed since the last accrual
+    ///
+    /// The fee is taken by dilution: shares worth `mgmt_fee_bps * elapsed /
+    /// year` of NAV are added to `total_shares` (and `unminted_mgmt_fee_shares`
+    /// until they are minted). Nothing accrues once Closed.
+    /// Returns the number of shares accrued.
+    ///
+    /// Handlers call this through `fees::MgmtFeeAccounts::accrue`, which mints
+    /// the accrued shares in the same instruction.
+    pub fn accrue_management_fee(&mut self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.last_mgmt_accrual_ts);
+        if elapsed <= 0 {
+            return Ok(0);
+        }
+        self.last_mgmt_accrual_ts = now;
+
+        if self.mgmt_fee_bps == 0 || self.total_shares == 0 || self.stage == FundStage::Closed {
+            return Ok(0);
+        }
+
+        // fee fraction f = bps * elapsed / (10_000 * year)
+        // new shares s satisfy s / (total + s) = f  =>  s = total * f / (1 - f)
+        let fee_numerator = (self.mgmt_fee_bps as u128) * (elapsed as u128);
+        let denominator = 10_000u128 * (SECONDS_PER_YEAR as u128);
+        if fee_numerator >= denominator {
+            return Ok(0);
+        }
+        let shares = (self.total_shares as u128) * fee_numerator / (denominator - fee_numerator);
+        let shares = u64::try_from(shares).map_err(|_| FundError::MathOverflow)?;
+
+        self.total_shares = self.total_shares.checked_add(shares)
+            .ok_or(FundError::MathOverflow)?;
+        self.unminted_mgmt_fee_shares = self.unminted_mgmt_fee_shares.checked_add(shares)
+            .ok_or(FundError::MathOverflow)?;
+
+        Ok(shares)
+    }
+
+    /// Crystallized performance fee still owed to the manager
+    pub fn outstanding_perf_fee(&self) -> u64 {
+        if self.perf_fee_paid {
+            0
+        } else {
+            self.perf_fee_due_usdc
+        }
+    }
+
+    /// Performance fee on NAV per share above the high-water mark
+    /// `nav` must already be net of the outstanding fee
+    pub fn perf_fee_above_hwm(&self, nav: u64) -> u64 {
+        let nav_per_share = self.nav_per_share(nav);
+        if self.total_shares == 0 || nav_per_share <= self.high_water_mark {
+            return 0;
+        }
+        let gain = ((nav_per_share - self.high_water_mark) as u128)
+            * (self.total_shares as u128) / 1_000_000;
+        self.calculate_perf_fee(gain.min(u64::MAX as u128) as u64)
+    }
+
+    /// Restart the high-water mark at `nav_per_share` when the fund has no
+    /// shares: a mark set by holders who have all left does not bind new ones
+    pub fn restart_hwm_if_empty(&mut self, nav_per_share: u64) {
+        if self.total_shares == 0 {
+            self.high_water_mark = nav_per_share;
+        }
+    }
+
+    /// Crystallize the performance fee above the high-water mark and raise the
+    /// mark to the post-fee NAV per share. Returns (fee, NAV after the fee).
+    pub fn crystallize_perf_fee(&mut self, nav: u64) -> Result<(u64, u64)> {
+        let fee = self.perf_fee_above_hwm(nav);
+        if fee == 0 {
+            return Ok((0, nav));
+        }
+
+        if self.perf_fee_paid {
+            self.perf_fee_due_usdc = 0;
+            self.perf_fee_paid = false;
+        }
+        self.perf_fee_due_usdc = self.perf_fee_due_usdc.checked_add(fee)
+            .ok_or(FundError::MathOverflow)?;
+
+        let nav_after = nav.saturating_sub(fee);
+        self.high_water_mark = self.high_water_mark.max(self.nav_per_share(nav_after));
+        Ok((fee, nav_after))
+    }
+
     /// Calculate early exit fee
     pub fn calculate_early_exit_fee(&self, amount: u64) -> u64 {
         ((amount as u128) * (self.early_exit_fee_bps as u128) / 10_000) as u64
@@ -221,6 +527,36 @@ impl FundState {
         ((nav as u128) * (self.liquidity_buffer_bps as u128) / 10_000) as u64
     }
 
+    /// NAV per share below which the drawdown breaker trips
+    pub fn drawdown_floor_per_share(&self) -> u64 {
+        let max_drawdown_bps = self.max_drawdown_bps.min(10_000) as u128;
+        ((self.start_nav_per_share as u128) * (10_000 - max_drawdown_bps) / 10_000) as u64
+    }
+
+    /// Whether NAV per share has fallen below the drawdown floor
+    pub fn drawdown_breached(&self, nav: u64) -> bool {
+        self.max_drawdown_bps > 0 && self.nav_per_share(nav) < self.drawdown_floor_per_share()
+    }
+
+    /// Max USDC that can leave the vault while keeping the liquidity buffer
+    /// on the post-withdrawal NAV: vault - x >= buffer_bps * (nav - x)
+    pub fn redeemable_liquidity(&self, vault_usdc: u64, nav: u64) -> u64 {
+        let buffer_bps = self.liquidity_buffer_bps.min(10_000) as u128;
+        if buffer_bps == 10_000 {
+            return 0;
+        }
+        let free = ((vault_usdc as u128) * 10_000).saturating_sub((nav as u128) * buffer_bps);
+        (free / (10_000 - buffer_bps)).min(vault_usdc as u128) as u64
+    }
+
+    /// Max USDC that may be redeemed in one epoch under the redemption gate
+    pub fn epoch_redemption_cap(&self, nav: u64) -> u64 {
+        if self.max_epoch_redemption_bps == 0 {
+            return u64::MAX;
+        }
+        ((nav as u128) * (self.max_epoch_redemption_bps.min(10_000) as u128) / 10_000) as u64
+    }
+
     /// Check if buffer is sufficient for a withdrawal
     pub fn buffer_sufficient(&self, vault_usdc: u64, nav: u64, withdrawal_amount: u64) -> bool {
         let min_buffer = self.min_buffer_amount(nav);
@@ -241,10 +577,16 @@ pub struct WithdrawalRequest {
     pub shares_filled: u64,
     /// USDC already received
     pub usdc_received: u64,
-    /// NAV per share locked at request time
+    /// NAV per share at request time (fill price under LockedAtRequest pricing)
     pub nav_per_share_at_request: u64,
     /// Unix timestamp when requested
     pub requested_at: i64,
+    /// Queue sequence number (used in the request PDA seeds)
+    pub sequence: u64,
+    /// Epoch in which the request was submitted
+    pub epoch: u64,
+    /// Last epoch in which the request received its pro-rata fill (0 = never)
+    pub last_filled_epoch: u64,
     /// Current status
     pub status: RequestStatus,
     /// PDA bump
@@ -260,6 +602,9 @@ impl WithdrawalRequest {
         8 +   // usdc_received
         8 +   // nav_per_share_at_request
         8 +   // requested_at
+        8 +   // sequence
+        8 +   // epoch
+        8 +   // last_filled_epoch
         1 +   // status
         1;    // bump
 
@@ -267,6 +612,537 @@ impl WithdrawalRequest {
     pub fn shares_remaining(&self) -> u64 {
         self.shares_requested.saturating_sub(self.shares_filled)
     }
+
+    /// Whether the request will receive no further fills
+    pub fn is_terminal(&self) -> bool {
+        self.status == RequestStatus::Completed || self.status == RequestStatus::Cancelled
+    }
+
+    /// USDC value of the remaining shares at the request-time NAV per share
+    pub fn locked_value(&self) -> u64 {
+        ((self.shares_remaining() as u128) * (self.nav_per_share_at_request as u128) / 1_000_000) as u64
+    }
+
+    /// NAV per share the request is filled at for the given epoch
+    pub fn fill_price(&self, pricing: WithdrawalPricing, epoch: &EpochState) -> u64 {
+        match pricing {
+            WithdrawalPricing::ForwardEpochNav => epoch.nav_per_share,
+            WithdrawalPricing::LockedAtRequest => self.nav_per_share_at_request,
+        }
+    }
+}
+
+/// Fee ledger - one per fund
+/// Manager and protocol fees are held in the fund's fee escrow and tracked
+/// here until claimed, along with lifetime totals
+#[account]
+pub struct FeeLedger {
+    /// Fund this ledger belongs to
+    pub fund: Pubkey,
+    /// Manager fees accrued and not yet claimed
+    pub manager_unclaimed: u64,
+    /// Protocol fees accrued and not yet claimed
+    pub protocol_unclaimed: u64,
+    /// Lifetime manager fees (after the protocol share)
+    pub manager_lifetime: u64,
+    /// Lifetime protocol fees
+    pub protocol_lifetime: u64,
+    /// Lifetime deposit fees (gross)
+    pub deposit_fees_lifetime: u64,
+    /// Lifetime performance fees (gross)
+    pub perf_fees_lifetime: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl FeeLedger {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // fund
+        8 +   // manager_unclaimed
+        8 +   // protocol_unclaimed
+        8 +   // manager_lifetime
+        8 +   // protocol_lifetime
+        8 +   // deposit_fees_lifetime
+        8 +   // perf_fees_lifetime
+        1;    // bump
+
+    /// Record a deposit fee paid into the fee escrow
+    pub fn credit_deposit_fee(&mut self, fee: u64, protocol_fee: u64) -> Result<()> {
+        self.deposit_fees_lifetime = self.deposit_fees_lifetime.checked_add(fee)
+            .ok_or(FundError::MathOverflow)?;
+        self.credit(fee, protocol_fee)
+    }
+
+    /// Record a performance fee paid into the fee escrow
+    pub fn credit_perf_fee(&mut self, fee: u64, protocol_fee: u64) -> Result<()> {
+        self.perf_fees_lifetime = self.perf_fees_lifetime.checked_add(fee)
+            .ok_or(FundError::MathOverflow)?;
+        self.credit(fee, protocol_fee)
+    }
+
+    fn credit(&mut self, fee: u64, protocol_fee: u64) -> Result<()> {
+        let manager_fee = fee.checked_sub(protocol_fee)
+            .ok_or(FundError::MathUnderflow)?;
+        self.manager_unclaimed = self.manager_unclaimed.checked_add(manager_fee)
+            .ok_or(FundError::MathOverflow)?;
+        self.protocol_unclaimed = self.protocol_unclaimed.checked_add(protocol_fee)
+            .ok_or(FundError::MathOverflow)?;
+        self.manager_lifetime = self.manager_lifetime.checked_add(manager_fee)
+            .ok_or(FundError::MathOverflow)?;
+        self.protocol_lifetime = self.protocol_lifetime.checked_add(protocol_fee)
+            .ok_or(FundError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Season summary - one per fund per season, written by finalize_close
+#[account]
+pub struct SeasonSummary {
+    /// Fund this season belongs to
+    pub fund: Pubkey,
+    /// Season number
+    pub season: u32,
+    /// Trading window of the season
+    pub trading_start_ts: i64,
+    pub trading_end_ts: i64,
+    /// Unix timestamp when the season was finalized
+    pub closed_at: i64,
+    /// AUM at the start of the season (informational)
+    pub initial_aum_usdc: u64,
+    /// NAV (vault USDC) at finalize
+    pub final_nav_usdc: u64,
+    /// Shares outstanding at finalize
+    pub total_shares: u64,
+    /// NAV per share (1e6 scale) at finalize, net of the performance fee
+    pub nav_per_share: u64,
+    /// High-water mark (NAV per share, 1e6 scale) after finalize
+    pub high_water_mark: u64,
+    /// Performance fee charged for the season
+    pub perf_fee_usdc: u64,
+    /// Whether the drawdown breaker ended the season early
+    pub breaker_tripped: bool,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SeasonSummary {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // fund
+        4 +   // season
+        8 +   // trading_start_ts
+        8 +   // trading_end_ts
+        8 +   // closed_at
+        8 +   // initial_aum_usdc
+        8 +   // final_nav_usdc
+        8 +   // total_shares
+        8 +   // nav_per_share
+        8 +   // high_water_mark
+        8 +   // perf_fee_usdc
+        1 +   // breaker_tripped
+        1;    // bump
+}
+
+/// Deposit request - one per investor per fund per epoch
+/// USDC is escrowed until the epoch is struck, then shares are minted at the
+/// struck NAV per share (after the deposit fee)
+#[account]
+pub struct DepositRequest {
+    /// Fund this request belongs to
+    pub fund: Pubkey,
+    /// Investor subscribing
+    pub investor: Pubkey,
+    /// Epoch whose strike prices the subscription
+    pub epoch: u64,
+    /// Gross USDC escrowed (before deposit fee)
+    pub usdc_amount: u64,
+    /// Unix timestamp of the last top-up
+    pub requested_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl DepositRequest {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // fund
+        32 +  // investor
+        8 +   // epoch
+        8 +   // usdc_amount
+        8 +   // requested_at
+        1;    // bump
+}
+
+/// FIFO withdrawal queue - one per fund
+/// Requests get monotonic sequence numbers from `tail`; `head` is the oldest
+/// request that may still be active. Every request below `head` is completed
+/// or cancelled and can be closed.
+#[account]
+pub struct WithdrawalQueue {
+    /// Fund this queue belongs to
+    pub fund: Pubkey,
+    /// Sequence of the oldest possibly active request
+    pub head: u64,
+    /// Sequence assigned to the next request
+    pub tail: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl WithdrawalQueue {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // fund
+        8 +   // head
+        8 +   // tail
+        1;    // bump
+
+    /// Number of requests between head and tail
+    pub fn len(&self) -> u64 {
+        self.tail.saturating_sub(self.head)
+    }
+
+    /// Whether no request is queued
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Move the head past `request` if it sits at the head and is terminal
+    pub fn advance_head(&mut self, request: &WithdrawalRequest) {
+        if request.sequence == self.head && request.is_terminal() {
+            self.head += 1;
+        }
+    }
+}
+
+/// Epoch snapshot - one per fund per epoch, struck by process_epoch
+/// All withdrawal fills of the epoch use the same pro-rata ratio, even when
+/// the queue is processed across several transactions
+#[account]
+pub struct EpochState {
+    /// Fund this epoch belongs to
+    pub fund: Pubkey,
+    /// Epoch number
+    pub epoch: u64,
+    /// Unix timestamp when the epoch was struck
+    pub struck_at: i64,
+    /// Fund NAV at strike
+    pub nav: u64,
+    /// NAV per share (1e6 scale) struck at epoch close
+    pub nav_per_share: u64,
+    /// Pending withdrawal shares at strike
+    pub pending_shares: u64,
+    /// USDC available for redemptions this epoch (respects the buffer and gate)
+    pub usdc_available: u64,
+    /// USDC held back by the epoch redemption gate (rolled to the next epoch)
+    pub gated_usdc: u64,
+    /// Fraction of each request's remaining shares filled (RATIO_SCALE = 100%)
+    pub fill_ratio: u64,
+    /// Shares filled so far this epoch
+    pub shares_filled: u64,
+    /// USDC paid so far this epoch
+    pub usdc_paid: u64,
+    /// PDA bump
+    pub bump: u8,
+    /// Sequence of the next request to fill (batches walk the queue in order)
+    pub cursor: u64,
+}
+
+impl EpochState {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // fund
+        8 +   // epoch
+        8 +   // struck_at
+        8 +   // nav
+        8 +   // nav_per_share
+        8 +   // pending_shares
+        8 +   // usdc_available
+        8 +   // gated_usdc
+        8 +   // fill_ratio
+        8 +   // shares_filled
+        8 +   // usdc_paid
+        1 +   // bump
+        8;    // cursor
+
+    /// USDC still available for fills this epoch
+    pub fn usdc_remaining(&self) -> u64 {
+        self.usdc_available.saturating_sub(self.usdc_paid)
+    }
+
+    /// Shares minted for a net subscription at the struck NAV per share
+    /// (zero only when shares existed but NAV was zero at the strike)
+    pub fn shares_for_subscription(&self, net_amount: u64) -> Result<u64> {
+        require!(self.nav_per_share > 0, FundError::ZeroNav);
+        Ok(((net_amount as u128) * 1_000_000 / (self.nav_per_share as u128)) as u64)
+    }
+}
+
+/// Outcome token position - one per fund per outcome mint
+/// Created and updated by execute_trade
+#[account]
+pub struct Position {
+    /// Fund this position belongs to
+    pub fund: Pubkey,
+    /// DFlow market ledger the outcome token belongs to
+    pub market_ledger: Pubkey,
+    /// Outcome token mint
+    pub outcome_mint: Pubkey,
+    /// Outcome side of the token
+    pub side: Side,
+    /// Outcome tokens currently held
+    pub quantity: u64,
+    /// USDC cost basis of the held quantity (average cost method)
+    pub cost_basis_usdc: u64,
+    /// Realized PnL in USDC across all reductions
+    pub realized_pnl: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Position {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // fund
+        32 +  // market_ledger
+        32 +  // outcome_mint
+        1 +   // side
+        8 +   // quantity
+        8 +   // cost_basis_usdc
+        8 +   // realized_pnl
+        1;    // bump
+
+    pub fn is_open(&self) -> bool {
+        self.quantity > 0
+    }
+
+    /// Average cost per whole outcome token in micro-USDC
+    pub fn average_cost(&self) -> u64 {
+        if self.quantity == 0 {
+            0
+        } else {
+            ((self.cost_basis_usdc as u128) * (MAX_OUTCOME_PRICE as u128) / (self.quantity as u128)) as u64
+        }
+    }
+
+    /// Add bought tokens at the given USDC cost
+    pub fn apply_buy(&mut self, quantity: u64, cost_usdc: u64) -> Result<()> {
+        self.quantity = self.quantity.checked_add(quantity)
+            .ok_or(FundError::MathOverflow)?;
+        self.cost_basis_usdc = self.cost_basis_usdc.checked_add(cost_usdc)
+            .ok_or(FundError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Track outcome tokens sent to the vault ATA outside a trade at zero cost
+    /// so sells and redemptions can clear them. Returns the quantity added.
+    pub fn sync_balance(&mut self, ata_balance: u64) -> u64 {
+        let untracked = ata_balance.saturating_sub(self.quantity);
+        self.quantity += untracked;
+        untracked
+    }
+
+    /// Remove sold (or redeemed) tokens and realize PnL against average cost
+    pub fn apply_sell(&mut self, quantity: u64, proceeds_usdc: u64) -> Result<()> {
+        require!(quantity <= self.quantity, FundError::InsufficientPosition);
+
+        let cost_removed = ((self.cost_basis_usdc as u128) * (quantity as u128)
+            / (self.quantity as u128)) as u64;
+        let pnl = (proceeds_usdc as i128) - (cost_removed as i128);
+
+        self.quantity -= quantity;
+        self.cost_basis_usdc = self.cost_basis_usdc.checked_sub(cost_removed)
+            .ok_or(FundError::MathUnderflow)?;
+        self.realized_pnl = (self.realized_pnl as i128)
+            .checked_add(pnl)
+            .and_then(|v| i64::try_from(v).ok())
+            .ok_or(FundError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Protocol allowlist entry - one per DFlow market ledger
+/// Managed by the protocol admin
+#[account]
+pub struct AllowedMarket {
+    /// DFlow market ledger account
+    pub market_ledger: Pubkey,
+    /// YES outcome token mint
+    pub yes_mint: Pubkey,
+    /// NO outcome token mint
+    pub no_mint: Pubkey,
+    /// Market category
+    pub category: MarketCategory,
+    /// Allowlist status
+    pub status: MarketStatus,
+    /// Unix timestamp when listed
+    pub listed_at: i64,
+    /// PDA bump
+    pub bump: u8,
+    /// Outcome mint that redeems at par once Resolved (default until then)
+    pub winning_mint: Pubkey,
+    /// Fund positions currently open in this market (blocks delisting)
+    pub open_positions: u32,
+}
+
+impl AllowedMarket {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // market_ledger
+        32 +  // yes_mint
+        32 +  // no_mint
+        1 +   // category
+        1 +   // status
+        8 +   // listed_at
+        1 +   // bump
+        32 +  // winning_mint
+        4;    // open_positions
+}
+
+/// Strategy mandate of a fund - set during Open, enforced by execute_trade
+/// Zero / empty values mean "unconstrained"
+#[account]
+pub struct FundMandate {
+    /// Fund this mandate belongs to
+    pub fund: Pubkey,
+    /// Allowed market categories as a `MarketCategory` bitmask
+    pub allowed_categories: u8,
+    /// Explicit list of tradable outcome mints
+    pub allowed_mints: Vec<Pubkey>,
+    /// Max cost basis in a single market (YES and NO combined) in bps of NAV
+    pub max_position_bps: u16,
+    /// Max number of concurrent open positions
+    pub max_open_positions: u16,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl FundMandate {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // fund
+        1 +   // allowed_categories
+        4 + 32 * MAX_MANDATE_MINTS + // allowed_mints
+        2 +   // max_position_bps
+        2 +   // max_open_positions
+        1;    // bump
+
+    /// Check that a market category and outcome mint are within the mandate
+    pub fn allows_market(&self, category: MarketCategory, outcome_mint: &Pubkey) -> bool {
+        let category_ok = self.allowed_categories == 0
+            || self.allowed_categories & category.mask() != 0;
+        let mint_ok = self.allowed_mints.is_empty() || self.allowed_mints.contains(outcome_mint);
+        category_ok && mint_ok
+    }
+}
+
+/// Mark price of an outcome token - one per outcome mint
+/// Written by the protocol oracle authority
+#[account]
+pub struct MarketPrice {
+    /// Outcome token mint
+    pub outcome_mint: Pubkey,
+    /// Mark price in micro-USDC per outcome token
+    pub price: u64,
+    /// Confidence interval in micro-USDC
+    pub confidence: u64,
+    /// Unix timestamp of the last update
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl MarketPrice {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // outcome_mint
+        8 +   // price
+        8 +   // confidence
+        8 +   // updated_at
+        1;    // bump
+
+    /// Price in micro-USDC per token, if fresh and tight enough
+    /// Confidence is bounded against the 1 USDC payout, not the price, so
+    /// cheap outcomes are not held to a tighter absolute band
+    pub fn checked_price(&self, now: i64, max_staleness_secs: i64) -> Result<u64> {
+        require!(
+            now.saturating_sub(self.updated_at) <= max_staleness_secs,
+            FundError::StalePrice
+        );
+        require!(
+            (self.confidence as u128) * 10_000
+                <= (MAX_OUTCOME_PRICE as u128) * (MAX_PRICE_CONFIDENCE_BPS as u128),
+            FundError::PriceConfidenceTooWide
+        );
+        Ok(self.price)
+    }
+}
+
+/// Fraction of pending redemption value an epoch can pay (RATIO_SCALE = 100%)
+pub fn pro_rata_ratio(usdc_available: u64, pending_value: u64) -> u64 {
+    if pending_value == 0 {
+        RATIO_SCALE
+    } else {
+        ((usdc_available as u128) * (RATIO_SCALE as u128) / (pending_value as u128))
+            .min(RATIO_SCALE as u128) as u64
+    }
+}
+
+/// Shares and USDC of a pro-rata fill of `shares_remaining` at `usdc_per_share`
+/// (1e6 scale), capped by `usdc_budget`. A leftover too small to ever be worth
+/// one micro-USDC is filled too, so every request can reach Completed.
+pub fn pro_rata_fill(
+    shares_remaining: u64,
+    fill_ratio: u64,
+    usdc_per_share: u64,
+    usdc_budget: u64,
+) -> (u64, u64) {
+    if usdc_per_share == 0 {
+        return (0, 0);
+    }
+    let value = |shares: u64| ((shares as u128) * (usdc_per_share as u128) / 1_000_000) as u64;
+
+    let mut shares = ((shares_remaining as u128) * (fill_ratio as u128) / (RATIO_SCALE as u128)) as u64;
+    if value(shares_remaining - shares) == 0 {
+        shares = shares_remaining;
+    }
+
+    let mut payout = value(shares);
+    if payout > usdc_budget {
+        payout = usdc_budget;
+        shares = ((payout as u128) * 1_000_000 / (usdc_per_share as u128)) as u64;
+    }
+    (shares, payout)
+}
+
+/// Fail with `OperationPaused` if `flag` is paused protocol-wide or on the fund
+pub fn require_not_paused(config: &ProtocolConfig, fund: &FundState, flag: u8) -> Result<()> {
+    require!(config.paused & flag == 0, FundError::OperationPaused);
+    require!((fund.paused | fund.admin_paused) & flag == 0, FundError::OperationPaused);
+    Ok(())
+}
+
+/// Like `require_not_paused`, but ignores the manager's pause mask
+/// Used by paths that hand escrowed funds back to investors
+pub fn require_not_admin_paused(config: &ProtocolConfig, fund: &FundState, flag: u8) -> Result<()> {
+    require!(config.paused & flag == 0, FundError::OperationPaused);
+    require!(fund.admin_paused & flag == 0, FundError::OperationPaused);
+    Ok(())
+}
+
+/// Deserialize an account passed through remaining_accounts
+/// Checks the owning program and the account discriminator
+pub fn load_account<T: AccountDeserialize + Owner>(info: &AccountInfo) -> Result<T> {
+    if info.owner != &T::owner() {
+        return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    let data = info.try_borrow_data()?;
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Close an account passed through remaining_accounts, sending its rent to
+/// `sol_destination`
+pub fn close_account<'info>(info: &AccountInfo<'info>, sol_destination: &AccountInfo<'info>) -> Result<()> {
+    let dest_starting_lamports = sol_destination.lamports();
+    **sol_destination.lamports.borrow_mut() = dest_starting_lamports
+        .checked_add(info.lamports())
+        .ok_or(FundError::MathOverflow)?;
+    **info.lamports.borrow_mut() = 0;
+
+    info.assign(&anchor_lang::system_program::ID);
+    info.realloc(0, false).map_err(Into::into)
 }
 
 /// Seeds for protocol config PDA
@@ -284,7 +1160,367 @@ pub const SHARE_MINT_SEED: &[u8] = b"share_mint";
 /// Seeds for withdrawal request PDA
 pub const WITHDRAWAL_REQUEST_SEED: &[u8] = b"withdrawal_request";
 
+/// Seeds for fee ledger PDA
+pub const FEE_LEDGER_SEED: &[u8] = b"fee_ledger";
+
+/// Seeds for the fee escrow token account PDA
+pub const FEE_ESCROW_SEED: &[u8] = b"fee_escrow";
+
+/// Seeds for the management fee share escrow token account PDA
+pub const MGMT_FEE_ESCROW_SEED: &[u8] = b"mgmt_fee_escrow";
+
+/// Seeds for season summary PDA
+pub const SEASON_SEED: &[u8] = b"season";
+
+/// Seeds for deposit request PDA
+pub const DEPOSIT_REQUEST_SEED: &[u8] = b"deposit_request";
+
+/// Seeds for the deposit escrow token account PDA
+pub const DEPOSIT_ESCROW_SEED: &[u8] = b"deposit_escrow";
+
+/// Seeds for withdrawal queue PDA
+pub const WITHDRAWAL_QUEUE_SEED: &[u8] = b"withdrawal_queue";
+
+/// Seeds for epoch state PDA
+pub const EPOCH_SEED: &[u8] = b"epoch";
+
+/// Scale of EpochState::fill_ratio (1e9 = 100%)
+pub const RATIO_SCALE: u64 = 1_000_000_000;
+
+/// Seeds for position PDA
+pub const POSITION_SEED: &[u8] = b"position";
+
+/// Seeds for market price PDA
+pub const MARKET_PRICE_SEED: &[u8] = b"market_price";
+
+/// Seeds for allowed market PDA
+pub const ALLOWED_MARKET_SEED: &[u8] = b"allowed_market";
+
+/// Seeds for fund mandate PDA
+pub const MANDATE_SEED: &[u8] = b"mandate";
+
+/// Maximum number of explicit outcome mints in a mandate
+pub const MAX_MANDATE_MINTS: usize = 16;
+
+/// Maximum price of an outcome token in micro-USDC (1 USDC pays out per winning token)
+pub const MAX_OUTCOME_PRICE: u64 = 1_000_000;
+
+/// Decimals of outcome token mints - prices are per whole token, so outcome
+/// amounts must share USDC's 6 decimals
+pub const OUTCOME_TOKEN_DECIMALS: u8 = 6;
+
+/// Default maximum age of a MarketPrice used for NAV (5 minutes)
+pub const DEFAULT_PRICE_STALENESS_SECS: i64 = 300;
+
+/// Bounds on the configurable price staleness (1 minute to 1 hour)
+pub const MIN_PRICE_STALENESS_SECS: i64 = 60;
+pub const MAX_PRICE_STALENESS_SECS: i64 = 3600;
+
+/// Maximum confidence interval relative to the 1 USDC payout (2%)
+pub const MAX_PRICE_CONFIDENCE_BPS: u16 = 200;
+
+/// Seconds in a (365-day) year, used to pro-rate the management fee
+pub const SECONDS_PER_YEAR: i64 = 365 * 24 * 60 * 60;
+
+/// Hard caps on the protocol fee limits (update_protocol_config cannot exceed these)
+pub const HARD_MAX_DEPOSIT_FEE_BPS: u16 = 1000;  // 10%
+pub const HARD_MAX_PERF_FEE_BPS: u16 = 5000;     // 50%
+pub const HARD_MAX_MGMT_FEE_BPS: u16 = 500;      // 5%
+pub const HARD_MAX_EARLY_EXIT_FEE_BPS: u16 = 1000; // 10%
+pub const HARD_MAX_PROTOCOL_FEE_BPS: u16 = 5000;   // 50% of manager fees
+
+/// Bounds on epoch intervals (1 hour to 30 days)
+pub const MIN_EPOCH_INTERVAL_SECS: i64 = 3600;
+pub const MAX_EPOCH_INTERVAL_SECS: i64 = 30 * 86400;
+
+/// Pause flags (ProtocolConfig::paused, FundState::paused / admin_paused)
+pub const PAUSE_DEPOSITS: u8 = 1 << 0;
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 1;
+pub const PAUSE_TRADING: u8 = 1 << 2;
+pub const PAUSE_EPOCH: u8 = 1 << 3;
+pub const PAUSE_REDEMPTIONS: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 =
+    PAUSE_DEPOSITS | PAUSE_WITHDRAWALS | PAUSE_TRADING | PAUSE_EPOCH | PAUSE_REDEMPTIONS;
+
 /// Default values
-pub const DEFAULT_BUFFER_BPS: u16 = 1000;      // 10%
-pub const DEFAULT_EARLY_EXIT_FEE_BPS: u16 = 500; // 5%
+pub const DEFAULT_MAX_MGMT_FEE_BPS: u16 = 200;  // 2%
 pub const DEFAULT_EPOCH_INTERVAL_SECS: i64 = 86400; // 24 hours
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(quantity: u64, cost_basis_usdc: u64) -> Position {
+        Position {
+            fund: Pubkey::default(),
+            market_ledger: Pubkey::default(),
+            outcome_mint: Pubkey::default(),
+            side: Side::Yes,
+            quantity,
+            cost_basis_usdc,
+            realized_pnl: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn apply_sell_realizes_pnl_against_average_cost() {
+        let mut pos = position(100, 60);
+        pos.apply_sell(40, 30).unwrap();
+        assert_eq!(pos.quantity, 60);
+        assert_eq!(pos.cost_basis_usdc, 36);
+        assert_eq!(pos.realized_pnl, 6);
+
+        pos.apply_sell(60, 0).unwrap();
+        assert_eq!(pos.quantity, 0);
+        assert_eq!(pos.cost_basis_usdc, 0);
+        assert_eq!(pos.realized_pnl, -30);
+        assert!(!pos.is_open());
+    }
+
+    #[test]
+    fn apply_sell_clears_rounding_dust_on_full_close() {
+        let mut pos = position(3, 10);
+        pos.apply_sell(1, 3).unwrap();
+        assert_eq!(pos.cost_basis_usdc, 7);
+        pos.apply_sell(2, 7).unwrap();
+        assert_eq!(pos.quantity, 0);
+        assert_eq!(pos.cost_basis_usdc, 0);
+        assert_eq!(pos.realized_pnl, 0);
+    }
+
+    #[test]
+    fn donated_balance_can_be_sold() {
+        let mut pos = position(100, 60);
+        // Someone sent 50 tokens straight to the vault ATA
+        assert_eq!(pos.sync_balance(150), 50);
+        assert_eq!(pos.quantity, 150);
+        assert_eq!(pos.cost_basis_usdc, 60);
+
+        pos.apply_sell(150, 90).unwrap();
+        assert_eq!(pos.quantity, 0);
+        assert_eq!(pos.realized_pnl, 30);
+        assert!(!pos.is_open());
+    }
+
+    #[test]
+    fn sync_balance_never_lowers_quantity() {
+        let mut pos = position(100, 60);
+        assert_eq!(pos.sync_balance(80), 0);
+        assert_eq!(pos.quantity, 100);
+
+        // A flat position picks up a donation as a zero-cost holding
+        let mut flat = position(0, 0);
+        assert_eq!(flat.sync_balance(1), 1);
+        assert!(flat.is_open());
+        assert_eq!(flat.average_cost(), 0);
+    }
+
+    #[test]
+    fn apply_sell_rejects_more_than_held() {
+        let mut pos = position(10, 5);
+        assert_eq!(pos.apply_sell(11, 5).unwrap_err(), FundError::InsufficientPosition.into());
+        assert_eq!(pos.quantity, 10);
+    }
+
+    #[test]
+    fn apply_sell_rejects_pnl_overflow() {
+        let mut pos = position(10, 0);
+        pos.realized_pnl = i64::MAX;
+        assert_eq!(pos.apply_sell(10, 1).unwrap_err(), FundError::MathOverflow.into());
+    }
+
+    #[test]
+    fn pro_rata_ratio_caps_at_full_fill() {
+        assert_eq!(pro_rata_ratio(0, 0), RATIO_SCALE);
+        assert_eq!(pro_rata_ratio(50, 200), RATIO_SCALE / 4);
+        assert_eq!(pro_rata_ratio(500, 200), RATIO_SCALE);
+        assert_eq!(pro_rata_ratio(0, 200), 0);
+    }
+
+    #[test]
+    fn pro_rata_fill_scales_by_ratio() {
+        assert_eq!(pro_rata_fill(1_000_000, RATIO_SCALE, 2_000_000, u64::MAX), (1_000_000, 2_000_000));
+        assert_eq!(pro_rata_fill(1_000_000, RATIO_SCALE / 2, 2_000_000, u64::MAX), (500_000, 1_000_000));
+        assert_eq!(pro_rata_fill(1_000_000, 0, 2_000_000, u64::MAX), (0, 0));
+    }
+
+    #[test]
+    fn pro_rata_fill_absorbs_worthless_remainder() {
+        // One leftover share is worth 0.001 micro-USDC, so the request completes
+        assert_eq!(pro_rata_fill(10_000_001, RATIO_SCALE - 1, 1_000, u64::MAX), (10_000_001, 10_000));
+        // A leftover worth at least one micro-USDC stays queued
+        assert_eq!(pro_rata_fill(1_000_001, RATIO_SCALE - 1, 1_000_000, u64::MAX), (1_000_000, 1_000_000));
+    }
+
+    #[test]
+    fn pro_rata_fill_caps_payout_at_budget() {
+        assert_eq!(pro_rata_fill(1_000_000, RATIO_SCALE, 2_000_000, 500_000), (250_000, 500_000));
+        assert_eq!(pro_rata_fill(1_000_000, RATIO_SCALE, 2_000_000, 0), (0, 0));
+    }
+
+    #[test]
+    fn pro_rata_fill_skips_zero_nav() {
+        assert_eq!(pro_rata_fill(1_000_000, RATIO_SCALE, 0, u64::MAX), (0, 0));
+    }
+
+    /// Zeroed fund with `total_shares` shares and 1 USDC high-water mark
+    fn fund(total_shares: u64) -> FundState {
+        let mut fund = FundState::deserialize(&mut &[0u8; FundState::LEN][8..]).unwrap();
+        fund.total_shares = total_shares;
+        fund.high_water_mark = 1_000_000;
+        fund.perf_fee_bps = 2_000;
+        fund
+    }
+
+    #[test]
+    fn perf_fee_charged_only_above_hwm() {
+        assert_eq!(fund(1_000_000).perf_fee_above_hwm(1_500_000), 100_000);
+        assert_eq!(fund(1_000_000).perf_fee_above_hwm(1_000_000), 0);
+        assert_eq!(fund(1_000_000).perf_fee_above_hwm(900_000), 0);
+        assert_eq!(fund(1_000_000).perf_fee_above_hwm(0), 0);
+        assert_eq!(fund(0).perf_fee_above_hwm(1_500_000), 0);
+    }
+
+    #[test]
+    fn crystallize_raises_hwm_to_post_fee_nav() {
+        let mut fund = fund(1_000_000);
+        assert_eq!(fund.crystallize_perf_fee(1_500_000).unwrap(), (100_000, 1_400_000));
+        assert_eq!(fund.perf_fee_due_usdc, 100_000);
+        assert_eq!(fund.high_water_mark, 1_400_000);
+
+        // Same NAV again is not a new gain
+        assert_eq!(fund.crystallize_perf_fee(1_400_000).unwrap(), (0, 1_400_000));
+        // A drawdown leaves the mark in place
+        assert_eq!(fund.crystallize_perf_fee(0).unwrap(), (0, 0));
+        assert_eq!(fund.high_water_mark, 1_400_000);
+        assert_eq!(fund.outstanding_perf_fee(), 100_000);
+    }
+
+    #[test]
+    fn crystallize_accumulates_unpaid_fee() {
+        let mut fund = fund(1_000_000);
+        fund.crystallize_perf_fee(1_500_000).unwrap();
+        assert_eq!(fund.crystallize_perf_fee(1_900_000).unwrap(), (100_000, 1_800_000));
+        assert_eq!(fund.perf_fee_due_usdc, 200_000);
+        assert_eq!(fund.high_water_mark, 1_800_000);
+    }
+
+    #[test]
+    fn crystallize_after_payment_resets_due() {
+        let mut fund = fund(1_000_000);
+        fund.crystallize_perf_fee(1_500_000).unwrap();
+        fund.perf_fee_paid = true;
+        assert_eq!(fund.outstanding_perf_fee(), 0);
+
+        fund.crystallize_perf_fee(1_900_000).unwrap();
+        assert!(!fund.perf_fee_paid);
+        assert_eq!(fund.perf_fee_due_usdc, 100_000);
+        assert_eq!(fund.outstanding_perf_fee(), 100_000);
+    }
+
+    #[test]
+    fn crystallize_rejects_due_overflow() {
+        let mut fund = fund(1_000_000);
+        fund.perf_fee_due_usdc = u64::MAX;
+        assert_eq!(fund.crystallize_perf_fee(1_500_000).unwrap_err(), FundError::MathOverflow.into());
+    }
+
+    #[test]
+    fn hwm_restarts_only_for_an_empty_fund() {
+        let mut held = fund(1_000_000);
+        held.high_water_mark = 1_400_000;
+        held.restart_hwm_if_empty(1_000_000);
+        assert_eq!(held.high_water_mark, 1_400_000);
+
+        let mut empty = fund(0);
+        empty.high_water_mark = 1_400_000;
+        empty.restart_hwm_if_empty(1_000_000);
+        assert_eq!(empty.high_water_mark, 1_000_000);
+
+        // New investors at 1.0 pay the fee on gains above 1.0
+        empty.total_shares = 1_000_000;
+        assert_eq!(empty.perf_fee_above_hwm(1_200_000), 40_000);
+    }
+
+    #[test]
+    fn redeemable_liquidity_keeps_post_withdrawal_buffer() {
+        let mut fund = fund(1_000_000);
+        fund.liquidity_buffer_bps = 1_000;
+        // 200 - 111 = 89 >= 10% of (1_000 - 111)
+        assert_eq!(fund.redeemable_liquidity(200, 1_000), 111);
+        assert_eq!(fund.redeemable_liquidity(50, 1_000), 0);
+        // Zero NAV: the whole vault is free but never more than it holds
+        assert_eq!(fund.redeemable_liquidity(100, 0), 100);
+
+        fund.liquidity_buffer_bps = 0;
+        assert_eq!(fund.redeemable_liquidity(200, 1_000), 200);
+        fund.liquidity_buffer_bps = 10_000;
+        assert_eq!(fund.redeemable_liquidity(200, 1_000), 0);
+    }
+
+    fn mgmt_fund(total_shares: u64) -> FundState {
+        let mut fund = fund(total_shares);
+        fund.mgmt_fee_bps = 200;
+        fund.stage = FundStage::Trading;
+        fund
+    }
+
+    #[test]
+    fn mgmt_fee_dilutes_by_annual_rate() {
+        let mut fund = mgmt_fund(1_000_000_000);
+        // s / (total + s) = 2%  =>  s = 1e9 * 200 / 9_800
+        assert_eq!(fund.accrue_management_fee(SECONDS_PER_YEAR).unwrap(), 20_408_163);
+        assert_eq!(fund.total_shares, 1_020_408_163);
+        assert_eq!(fund.unminted_mgmt_fee_shares, 20_408_163);
+        assert_eq!(fund.last_mgmt_accrual_ts, SECONDS_PER_YEAR);
+    }
+
+    #[test]
+    fn mgmt_fee_skips_non_positive_elapsed() {
+        let mut fund = mgmt_fund(1_000_000_000);
+        fund.last_mgmt_accrual_ts = 100;
+        assert_eq!(fund.accrue_management_fee(100).unwrap(), 0);
+        assert_eq!(fund.accrue_management_fee(50).unwrap(), 0);
+        assert_eq!(fund.last_mgmt_accrual_ts, 100);
+        assert_eq!(fund.total_shares, 1_000_000_000);
+    }
+
+    #[test]
+    fn mgmt_fee_advances_clock_without_accruing() {
+        let mut closed = mgmt_fund(1_000_000_000);
+        closed.stage = FundStage::Closed;
+        assert_eq!(closed.accrue_management_fee(SECONDS_PER_YEAR).unwrap(), 0);
+        assert_eq!(closed.last_mgmt_accrual_ts, SECONDS_PER_YEAR);
+        assert_eq!(closed.total_shares, 1_000_000_000);
+
+        let mut empty = mgmt_fund(0);
+        assert_eq!(empty.accrue_management_fee(SECONDS_PER_YEAR).unwrap(), 0);
+        assert_eq!(empty.last_mgmt_accrual_ts, SECONDS_PER_YEAR);
+
+        // Fee-free time is not billed later when the fee is switched on
+        let mut free = mgmt_fund(1_000_000_000);
+        free.mgmt_fee_bps = 0;
+        free.accrue_management_fee(SECONDS_PER_YEAR).unwrap();
+        free.mgmt_fee_bps = 200;
+        assert_eq!(free.accrue_management_fee(SECONDS_PER_YEAR).unwrap(), 0);
+    }
+
+    #[test]
+    fn mgmt_fee_ignores_full_dilution() {
+        let mut fund = mgmt_fund(1_000_000_000);
+        fund.mgmt_fee_bps = 10_000;
+        assert_eq!(fund.accrue_management_fee(SECONDS_PER_YEAR).unwrap(), 0);
+        assert_eq!(fund.total_shares, 1_000_000_000);
+    }
+
+    #[test]
+    fn mgmt_fee_rejects_share_overflow() {
+        let mut fund = mgmt_fund(u64::MAX);
+        assert_eq!(fund.accrue_management_fee(SECONDS_PER_YEAR).unwrap_err(), FundError::MathOverflow.into());
+
+        let mut fund = mgmt_fund(1_000_000_000);
+        fund.unminted_mgmt_fee_shares = u64::MAX;
+        assert_eq!(fund.accrue_management_fee(SECONDS_PER_YEAR).unwrap_err(), FundError::MathOverflow.into());
+    }
+}